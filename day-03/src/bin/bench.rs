@@ -0,0 +1,80 @@
+use std::time::Instant;
+
+use day_03::{scan, Token};
+
+/// Times tokenizing a large random schematic. Takes the side length of the
+/// square schematic as an optional argument.
+fn main() {
+    let size = std::env::args()
+        .nth(1)
+        .map(|s| s.parse().unwrap())
+        .unwrap_or(10_000);
+
+    let input = generate_schematic(size, 0x2545f4914f6cdd1d);
+
+    let start = Instant::now();
+    let (mut numbers, mut symbols) = (0, 0);
+    for token in scan(&input) {
+        match token.unwrap() {
+            (_, Token::Number { .. }) => numbers += 1,
+            (_, Token::Symbol { .. }) => symbols += 1,
+        }
+    }
+    let elapsed = start.elapsed();
+
+    println!(
+        "Scanned {}x{} schematic in {:.1} ms ({:.0} MB/s)",
+        size,
+        size,
+        elapsed.as_secs_f64() * 1000.0,
+        input.len() as f64 / elapsed.as_secs_f64() / 1e6
+    );
+    println!("Numbers: {} symbols: {}", numbers, symbols);
+}
+
+/// A square schematic of mostly dots with short numbers and the odd symbol,
+/// from a xorshift generator so every run scans the same text.
+fn generate_schematic(size: usize, seed: u64) -> String {
+    let mut state = seed;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    let symbols = b"*#+$/=@%&-";
+    let mut input = Vec::with_capacity(size * (size + 1));
+    for y in 0..size {
+        if y > 0 {
+            input.push(b'\n');
+        }
+
+        let mut x = 0;
+        while x < size {
+            match next() % 10 {
+                0 => {
+                    let width = (1 + next() % 3) as usize;
+                    for _ in 0..width.min(size - x) {
+                        input.push(b'0' + (next() % 10) as u8);
+                    }
+                    x += width;
+                    if x < size {
+                        input.push(b'.');
+                        x += 1;
+                    }
+                }
+                1 => {
+                    input.push(symbols[next() as usize % symbols.len()]);
+                    x += 1;
+                }
+                _ => {
+                    input.push(b'.');
+                    x += 1;
+                }
+            }
+        }
+    }
+
+    String::from_utf8(input).unwrap()
+}
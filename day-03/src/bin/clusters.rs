@@ -0,0 +1,339 @@
+use std::collections::HashMap;
+use std::fs;
+
+use day_03::{scan, SchematicError, Token};
+
+fn main() {
+    let input = match std::env::args().nth(1) {
+        Some(path) => fs::read_to_string(&path).unwrap_or_else(|e| {
+            eprintln!("Error: {}: {}", path, e);
+            std::process::exit(1);
+        }),
+        None => include_str!("input.txt").to_string(),
+    };
+
+    if let Err(e) = report(&input) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn report(input: &str) -> Result<(), SchematicError> {
+    let entities = parse_board(input)?;
+    let links = link_entities(&entities);
+    let clusters = find_clusters(&entities, &links);
+
+    for (i, cluster) in clusters.iter().enumerate() {
+        let ((min_x, min_y), (max_x, max_y)) = cluster.bounds();
+        println!(
+            "Cluster {}: ({}, {})-({}, {}) numbers: {} symbols: {} sum: {}",
+            i + 1,
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+            cluster.number_count(),
+            cluster.symbol_count(),
+            cluster.number_sum()?
+        );
+
+        for member in cluster.members.iter() {
+            println!("  {}", member);
+        }
+    }
+
+    println!();
+    println!("Isolated numbers:");
+    for cluster in clusters.iter().filter(|c| c.is_isolated_number()) {
+        println!("  {}", cluster.members[0]);
+    }
+
+    println!("Shared symbols:");
+    for (symbol, numbers) in shared_symbols(&entities, &links) {
+        let numbers = numbers
+            .iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!("  {} -> {}", symbol, numbers);
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum BoardEntry {
+    Number(u64),
+    Symbol(char),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Entity {
+    entry: BoardEntry,
+    x: usize,
+    y: usize,
+    width: usize,
+}
+
+impl Entity {
+    fn new(entry: BoardEntry, x: usize, y: usize, width: usize) -> Self {
+        Self { entry, x, y, width }
+    }
+
+    fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.x..self.x + self.width).map(|x| (x, self.y))
+    }
+
+    fn neighbour_cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.y.saturating_sub(1)..=self.y + 1).flat_map(move |y| {
+            (self.x.saturating_sub(1)..=self.x + self.width).map(move |x| (x, y))
+        })
+    }
+}
+
+impl std::fmt::Display for Entity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.entry {
+            BoardEntry::Number(n) => write!(f, "{} @ ({}, {})", n, self.x, self.y),
+            BoardEntry::Symbol(c) => write!(f, "{} @ ({}, {})", c, self.x, self.y),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Cluster {
+    members: Vec<Entity>,
+}
+
+impl Cluster {
+    fn bounds(&self) -> ((usize, usize), (usize, usize)) {
+        let cells = self.members.iter().flat_map(|m| m.cells());
+
+        cells.fold(
+            ((usize::MAX, usize::MAX), (0, 0)),
+            |((min_x, min_y), (max_x, max_y)), (x, y)| {
+                ((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y)))
+            },
+        )
+    }
+
+    fn numbers(&self) -> impl Iterator<Item = u64> + '_ {
+        self.members.iter().flat_map(|m| match m.entry {
+            BoardEntry::Number(n) => Some(n),
+            BoardEntry::Symbol(_) => None,
+        })
+    }
+
+    fn number_count(&self) -> usize {
+        self.numbers().count()
+    }
+
+    fn symbol_count(&self) -> usize {
+        self.members.len() - self.number_count()
+    }
+
+    fn number_sum(&self) -> Result<u64, SchematicError> {
+        self.numbers()
+            .try_fold(0u64, |sum, n| sum.checked_add(n))
+            .ok_or(SchematicError::Overflow)
+    }
+
+    fn is_isolated_number(&self) -> bool {
+        self.members.len() == 1 && self.number_count() == 1
+    }
+}
+
+fn parse_board(input: &str) -> Result<Vec<Entity>, SchematicError> {
+    let mut entities: Vec<Entity> = vec![];
+
+    for token in scan(input) {
+        entities.push(match token? {
+            (y, Token::Number { x, width, value }) => {
+                Entity::new(BoardEntry::Number(value), x, y, width)
+            }
+            (y, Token::Symbol { x, symbol }) => Entity::new(BoardEntry::Symbol(symbol), x, y, 1),
+        });
+    }
+
+    Ok(entities)
+}
+
+/// Lists, for every entity, the indices of the entities it touches
+/// horizontally, vertically or diagonally.
+fn link_entities(entities: &[Entity]) -> Vec<Vec<usize>> {
+    let occupied: HashMap<(usize, usize), usize> = entities
+        .iter()
+        .enumerate()
+        .flat_map(|(i, e)| e.cells().map(move |cell| (cell, i)))
+        .collect();
+
+    entities
+        .iter()
+        .enumerate()
+        .map(|(i, entity)| {
+            let mut links = entity
+                .neighbour_cells()
+                .flat_map(|cell| occupied.get(&cell).copied())
+                .filter(|j| *j != i)
+                .collect::<Vec<_>>();
+            links.sort();
+            links.dedup();
+            links
+        })
+        .collect()
+}
+
+fn find_clusters(entities: &[Entity], links: &[Vec<usize>]) -> Vec<Cluster> {
+    let mut visited = vec![false; entities.len()];
+    let mut clusters = vec![];
+
+    for start in 0..entities.len() {
+        if visited[start] {
+            continue;
+        }
+
+        visited[start] = true;
+        let mut stack = vec![start];
+        let mut members = vec![];
+
+        while let Some(i) = stack.pop() {
+            members.push(i);
+            for j in links[i].iter() {
+                if !visited[*j] {
+                    visited[*j] = true;
+                    stack.push(*j);
+                }
+            }
+        }
+
+        members.sort();
+        clusters.push(Cluster {
+            members: members.iter().map(|i| entities[*i]).collect(),
+        });
+    }
+
+    clusters
+}
+
+/// Symbols touching more than one number, along with the numbers they touch.
+fn shared_symbols(entities: &[Entity], links: &[Vec<usize>]) -> Vec<(Entity, Vec<Entity>)> {
+    entities
+        .iter()
+        .zip(links.iter())
+        .filter(|(e, _)| matches!(e.entry, BoardEntry::Symbol(_)))
+        .map(|(symbol, links)| {
+            let numbers = links
+                .iter()
+                .map(|i| entities[*i])
+                .filter(|e| matches!(e.entry, BoardEntry::Number(_)))
+                .collect::<Vec<_>>();
+            (*symbol, numbers)
+        })
+        .filter(|(_, numbers)| numbers.len() > 1)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clusters(input: &str) -> Vec<Cluster> {
+        let entities = parse_board(input).unwrap();
+        let links = link_entities(&entities);
+        find_clusters(&entities, &links)
+    }
+
+    #[test]
+    fn test_parse_board() {
+        let input = concat!(
+            "467..\n",
+            "...*.\n",
+            "#.35."
+        );
+
+        assert_eq!(
+            parse_board(input).unwrap(),
+            vec![
+                Entity::new(BoardEntry::Number(467), 0, 0, 3),
+                Entity::new(BoardEntry::Symbol('*'), 3, 1, 1),
+                Entity::new(BoardEntry::Symbol('#'), 0, 2, 1),
+                Entity::new(BoardEntry::Number(35), 2, 2, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_link_entities() {
+        let input = concat!(
+            "467..\n",
+            "...*.\n",
+            "#.35."
+        );
+        let entities = parse_board(input).unwrap();
+
+        assert_eq!(
+            link_entities(&entities),
+            vec![vec![1], vec![0, 3], vec![], vec![1]]
+        );
+    }
+
+    #[test]
+    fn test_find_clusters() {
+        let input = include_str!("test1.txt");
+        let clusters = clusters(input);
+
+        assert_eq!(clusters.len(), 8);
+
+        let first = &clusters[0];
+        assert_eq!(first.bounds(), ((0, 0), (3, 2)));
+        assert_eq!(first.number_count(), 2);
+        assert_eq!(first.symbol_count(), 1);
+        assert_eq!(first.number_sum(), Ok(467 + 35));
+
+        let isolated = clusters
+            .iter()
+            .filter(|c| c.is_isolated_number())
+            .flat_map(|c| c.numbers())
+            .collect::<Vec<_>>();
+        assert_eq!(isolated, vec![114, 58]);
+    }
+
+    #[test]
+    fn test_diagonal_numbers_join() {
+        let input = concat!(
+            "12...\n",
+            "..34.\n",
+            "....+"
+        );
+        let clusters = clusters(input);
+
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].bounds(), ((0, 0), (4, 2)));
+        assert_eq!(clusters[0].number_sum(), Ok(46));
+    }
+
+    #[test]
+    fn test_long_numbers() {
+        let clusters = clusters("18446744073709551615*1");
+        assert_eq!(clusters[0].number_count(), 2);
+        assert_eq!(clusters[0].number_sum(), Err(SchematicError::Overflow));
+
+        assert_eq!(
+            parse_board("1.\n..99999999999999999999"),
+            Err(SchematicError::NumberTooLarge { x: 2, y: 1 })
+        );
+    }
+
+    #[test]
+    fn test_shared_symbols() {
+        let input = include_str!("test1.txt");
+        let entities = parse_board(input).unwrap();
+        let links = link_entities(&entities);
+
+        let shared = shared_symbols(&entities, &links)
+            .iter()
+            .map(|(s, numbers)| (s.x, s.y, numbers.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(shared, vec![(3, 1, 2), (5, 8, 2)]);
+    }
+}
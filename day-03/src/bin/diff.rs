@@ -0,0 +1,301 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fs;
+
+use day_03::{scan, SchematicError, Token};
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let (Some(old_path), Some(new_path)) = (args.next(), args.next()) else {
+        eprintln!("Usage: diff <old schematic> <new schematic>");
+        std::process::exit(1);
+    };
+
+    let (old, new) = (read(&old_path), read(&new_path));
+    if let Err(e) = report(&old, &new) {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Reads a schematic, exiting with the path and the reason if it can't be.
+fn read(path: &str) -> String {
+    fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("Error: {}: {}", path, e);
+        std::process::exit(1);
+    })
+}
+
+fn report(old: &str, new: &str) -> Result<(), SchematicError> {
+    let old = parse_schematic(old)?;
+    let new = parse_schematic(new)?;
+
+    for change in diff(&old, &new) {
+        match change {
+            Change::Added(number) => println!("+ {}", number),
+            Change::Removed(number) => println!("- {}", number),
+            Change::AdjacencyChanged(before, after) => {
+                println!("~ {} -> {}", before, format_symbols(&after.symbols))
+            }
+        }
+    }
+
+    let (before, after) = (old.part_number_sum()?, new.part_number_sum()?);
+    println!(
+        "Part number sum: {} -> {} ({:+})",
+        before,
+        after,
+        after as i128 - before as i128
+    );
+
+    let (before, after) = (old.gear_ratio_sum()?, new.gear_ratio_sum()?);
+    println!(
+        "Gear ratio sum: {} -> {} ({:+})",
+        before,
+        after,
+        after as i128 - before as i128
+    );
+
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Symbol {
+    x: usize,
+    y: usize,
+    symbol: char,
+}
+
+#[derive(Debug, PartialEq)]
+struct Number {
+    x: usize,
+    y: usize,
+    width: usize,
+    value: u64,
+    symbols: Vec<Symbol>,
+}
+
+impl Number {
+    fn is_part(&self) -> bool {
+        !self.symbols.is_empty()
+    }
+
+    fn touches(&self, symbol: &Symbol) -> bool {
+        symbol.y + 1 >= self.y
+            && symbol.y <= self.y + 1
+            && symbol.x + 1 >= self.x
+            && symbol.x <= self.x + self.width
+    }
+}
+
+impl std::fmt::Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} @ ({}, {}) {}",
+            self.value,
+            self.x,
+            self.y,
+            format_symbols(&self.symbols)
+        )
+    }
+}
+
+fn format_symbols(symbols: &[Symbol]) -> String {
+    let symbols = symbols
+        .iter()
+        .map(|s| format!("{} @ ({}, {})", s.symbol, s.x, s.y))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!("[{}]", symbols)
+}
+
+#[derive(Debug, PartialEq)]
+struct Schematic {
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+}
+
+impl Schematic {
+    fn part_number_sum(&self) -> Result<u64, SchematicError> {
+        self.numbers
+            .iter()
+            .filter(|n| n.is_part())
+            .try_fold(0u64, |sum, n| sum.checked_add(n.value))
+            .ok_or(SchematicError::Overflow)
+    }
+
+    fn gear_ratio_sum(&self) -> Result<u64, SchematicError> {
+        let mut gears: HashMap<(usize, usize), Vec<u64>> = HashMap::new();
+
+        for number in self.numbers.iter() {
+            for symbol in number.symbols.iter().filter(|s| s.symbol == '*') {
+                gears.entry((symbol.x, symbol.y)).or_default().push(number.value);
+            }
+        }
+
+        gears
+            .values()
+            .filter(|numbers| numbers.len() == 2)
+            .try_fold(0u64, |sum, numbers| {
+                sum.checked_add(numbers[0].checked_mul(numbers[1])?)
+            })
+            .ok_or(SchematicError::Overflow)
+    }
+}
+
+fn parse_schematic(input: &str) -> Result<Schematic, SchematicError> {
+    let mut numbers: Vec<Number> = vec![];
+    let mut symbols: Vec<Symbol> = vec![];
+
+    for token in scan(input) {
+        match token? {
+            (y, Token::Number { x, width, value }) => numbers.push(Number {
+                x,
+                y,
+                width,
+                value,
+                symbols: vec![],
+            }),
+            (y, Token::Symbol { x, symbol }) => symbols.push(Symbol { x, y, symbol }),
+        }
+    }
+
+    let mut rows: HashMap<usize, Vec<Symbol>> = HashMap::new();
+    for symbol in symbols.iter() {
+        rows.entry(symbol.y).or_default().push(*symbol);
+    }
+
+    for number in numbers.iter_mut() {
+        number.symbols = (number.y.saturating_sub(1)..=number.y + 1)
+            .flat_map(|y| rows.get(&y))
+            .flatten()
+            .filter(|s| number.touches(s))
+            .copied()
+            .collect();
+    }
+
+    Ok(Schematic { numbers, symbols })
+}
+
+#[derive(Debug, PartialEq)]
+enum Change<'a> {
+    Added(&'a Number),
+    Removed(&'a Number),
+    AdjacencyChanged(&'a Number, &'a Number),
+}
+
+type Alignment<'a> = (Option<&'a Number>, Option<&'a Number>);
+
+/// Aligns both schematics by the position and value of each number, keeping
+/// only the changes that affect part numbers.
+fn diff<'a>(old: &'a Schematic, new: &'a Schematic) -> Vec<Change<'a>> {
+    let mut aligned: BTreeMap<(usize, usize, u64), Alignment> = BTreeMap::new();
+
+    for number in old.numbers.iter() {
+        aligned.entry((number.y, number.x, number.value)).or_default().0 = Some(number);
+    }
+    for number in new.numbers.iter() {
+        aligned.entry((number.y, number.x, number.value)).or_default().1 = Some(number);
+    }
+
+    aligned
+        .into_values()
+        .flat_map(|pair| match pair {
+            (Some(before), None) if before.is_part() => Some(Change::Removed(before)),
+            (None, Some(after)) if after.is_part() => Some(Change::Added(after)),
+            (Some(before), Some(after)) if before.symbols != after.symbols => {
+                Some(Change::AdjacencyChanged(before, after))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_schematic() {
+        let input = concat!(
+            "467..\n",
+            "...*.\n",
+            "..35."
+        );
+        let schematic = parse_schematic(input).unwrap();
+
+        let star = Symbol { x: 3, y: 1, symbol: '*' };
+        assert_eq!(schematic.symbols, vec![star]);
+        assert_eq!(
+            schematic.numbers,
+            vec![
+                Number { x: 0, y: 0, width: 3, value: 467, symbols: vec![star] },
+                Number { x: 2, y: 2, width: 2, value: 35, symbols: vec![star] },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sums() {
+        let schematic = parse_schematic(include_str!("test1.txt")).unwrap();
+
+        assert_eq!(schematic.part_number_sum(), Ok(4361));
+        assert_eq!(schematic.gear_ratio_sum(), Ok(467835));
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = parse_schematic(concat!(
+            "467..114..\n",
+            "...*......\n",
+            "..35...633\n",
+            ".......#.."
+        ))
+        .unwrap();
+        let new = parse_schematic(concat!(
+            "467..114..\n",
+            "...*.*....\n",
+            "..35......\n",
+            ".......#.."
+        ))
+        .unwrap();
+
+        let changes = diff(&old, &new)
+            .iter()
+            .map(|c| match c {
+                Change::Added(n) => ('+', n.value),
+                Change::Removed(n) => ('-', n.value),
+                Change::AdjacencyChanged(n, _) => ('~', n.value),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(changes, vec![('~', 114), ('-', 633)]);
+
+        assert_eq!(old.part_number_sum(), Ok(467 + 35 + 633));
+        assert_eq!(new.part_number_sum(), Ok(467 + 114 + 35));
+        assert_eq!(old.gear_ratio_sum(), Ok(467 * 35));
+        assert_eq!(new.gear_ratio_sum(), Ok(467 * 35));
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        let old = parse_schematic(include_str!("test1.txt")).unwrap();
+        let new = parse_schematic(include_str!("test1.txt")).unwrap();
+
+        assert_eq!(diff(&old, &new), vec![]);
+    }
+
+    #[test]
+    fn test_long_numbers() {
+        let schematic = parse_schematic("18446744073709551615*2").unwrap();
+        assert_eq!(schematic.numbers[0].value, u64::MAX);
+        assert_eq!(schematic.part_number_sum(), Err(SchematicError::Overflow));
+        assert_eq!(schematic.gear_ratio_sum(), Err(SchematicError::Overflow));
+
+        assert_eq!(
+            parse_schematic("*99999999999999999999"),
+            Err(SchematicError::NumberTooLarge { x: 1, y: 0 })
+        );
+    }
+}
@@ -1,50 +1,39 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 
-use regex::Regex;
+use day_03::{scan, SchematicError, Token};
 
 fn main() {
     let input = include_str!("input.txt");
-    let result = part1(input);
 
-    println!("Result: {}", result);
+    match part1(input) {
+        Ok(result) => println!("Result: {}", result),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
 }
 
 enum BoardEntry {
-    Number(u32),
-    Symbol(char),
+    Number(u64),
+    Symbol,
 }
 
-fn part1(input: &str) -> u32 {
-    let lines = input.split("\n").collect::<Vec<&str>>();
-
-    let numbers_re = Regex::new(r"(\d+)").unwrap();
-    let symbols_re = Regex::new(r"([^0-9\.])").unwrap();
-
+fn part1(input: &str) -> Result<u64, SchematicError> {
     let mut board: HashMap<(usize, usize), BoardEntry> = HashMap::new();
 
-    for (y, line) in lines.iter().enumerate() {
-        for (x, number) in numbers_re.captures_iter(line)
-            .map(|c| {
-                let capture = c.get(0).unwrap();
-                (capture.start(), capture.as_str())
-            }) {
-            board.insert((x, y), BoardEntry::Number(number.parse().unwrap()));
-        }
-
-        for (x, symbol) in symbols_re.captures_iter(line)
-            .map(|c| {
-                let capture = c.get(0).unwrap();
-                (capture.start(), capture.as_str())
-            }) {
-            board.insert((x, y), BoardEntry::Symbol(symbol.chars().nth(0).unwrap()));
-        }
+    for token in scan(input) {
+        match token? {
+            (y, Token::Number { x, value, .. }) => board.insert((x, y), BoardEntry::Number(value)),
+            (y, Token::Symbol { x, .. }) => board.insert((x, y), BoardEntry::Symbol),
+        };
     }
 
-    let mut numbers: HashSet<(u32, usize, usize)> = HashSet::new();
+    let mut numbers: HashSet<(u64, usize, usize)> = HashSet::new();
 
     for (p, entry) in board.iter() {
-        if let BoardEntry::Symbol(_) = entry {
+        if let BoardEntry::Symbol = entry {
             let neighbours = [
                 (p.0 - 1, p.1 - 1), (p.0, p.1 - 1), (p.0 + 1, p.1 - 1),
                 (p.0 - 1, p.1), (p.0 + 1, p.1),
@@ -87,7 +76,10 @@ fn part1(input: &str) -> u32 {
         }
     }
 
-    numbers.iter().map(|(n, _, _)| n).sum()
+    numbers
+        .iter()
+        .try_fold(0u64, |sum, (n, _, _)| sum.checked_add(*n))
+        .ok_or(SchematicError::Overflow)
 }
 
 #[cfg(test)]
@@ -97,41 +89,50 @@ mod tests {
     #[test]
     fn test_part1() {
         let input = include_str!("test1.txt");
-        assert_eq!(part1(input), 4361);
+        assert_eq!(part1(input), Ok(4361));
 
         let input = concat!(
             ".123..\n",
             "....*.\n",
             "......"
         );
-        assert_eq!(part1(input), 123);
+        assert_eq!(part1(input), Ok(123));
         
         let input = concat!(
             "......\n",
             ".123*.\n",
             "......"
         );
-        assert_eq!(part1(input), 123);
+        assert_eq!(part1(input), Ok(123));
 
         let input = concat!(
             "......\n",
             "....*.\n",
             ".123.."
         );
-        assert_eq!(part1(input), 123);
+        assert_eq!(part1(input), Ok(123));
 
         let input = concat!(
             ".12...\n",
             ".12.*.\n",
             ".12..."
         );
-        assert_eq!(part1(input), 0);
+        assert_eq!(part1(input), Ok(0));
 
         let input = concat!(
             ".12..\n",
             ".12*.\n",
             "....."
         );
-        assert_eq!(part1(input), 24);
+        assert_eq!(part1(input), Ok(24));
+    }
+
+    #[test]
+    fn test_part1_number_too_large() {
+        let input = concat!(
+            "......\n",
+            "*99999999999999999999"
+        );
+        assert_eq!(part1(input), Err(SchematicError::NumberTooLarge { x: 1, y: 1 }));
     }
 }
@@ -1,51 +1,40 @@
 use std::collections::HashMap;
 use std::collections::HashSet;
 
-use regex::Regex;
+use day_03::{scan, SchematicError, Token};
 
 fn main() {
     let input = include_str!("input.txt");
-    let result = part2(input);
 
-    println!("Result: {}", result);
+    match part2(input) {
+        Ok(result) => println!("Result: {}", result),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
 }
 
 enum BoardEntry {
-    Number(u32),
+    Number(u64),
     Symbol(char),
 }
 
-fn part2(input: &str) -> u32 {
-    let lines = input.split("\n").collect::<Vec<&str>>();
-
-    let numbers_re = Regex::new(r"(\d+)").unwrap();
-    let symbols_re = Regex::new(r"([^0-9\.])").unwrap();
-
+fn part2(input: &str) -> Result<u64, SchematicError> {
     let mut board: HashMap<(usize, usize), BoardEntry> = HashMap::new();
 
-    for (y, line) in lines.iter().enumerate() {
-        for (x, number) in numbers_re.captures_iter(line)
-            .map(|c| {
-                let capture = c.get(0).unwrap();
-                (capture.start(), capture.as_str())
-            }) {
-            board.insert((x, y), BoardEntry::Number(number.parse().unwrap()));
-        }
-
-        for (x, symbol) in symbols_re.captures_iter(line)
-            .map(|c| {
-                let capture = c.get(0).unwrap();
-                (capture.start(), capture.as_str())
-            }) {
-            board.insert((x, y), BoardEntry::Symbol(symbol.chars().nth(0).unwrap()));
-        }
+    for token in scan(input) {
+        match token? {
+            (y, Token::Number { x, value, .. }) => board.insert((x, y), BoardEntry::Number(value)),
+            (y, Token::Symbol { x, symbol }) => board.insert((x, y), BoardEntry::Symbol(symbol)),
+        };
     }
 
-    let mut gear_ratios: Vec<u32> = vec![];
+    let mut gear_ratios: Vec<u64> = vec![];
 
     for (p, entry) in board.iter() {
         if let BoardEntry::Symbol('*') = entry {
-            let mut gears: HashSet<(u32, usize, usize)> = HashSet::new();
+            let mut gears: HashSet<(u64, usize, usize)> = HashSet::new();
 
             let neighbours = [
                 (p.0 - 1, p.1 - 1), (p.0, p.1 - 1), (p.0 + 1, p.1 - 1),
@@ -88,13 +77,20 @@ fn part2(input: &str) -> u32 {
             }
 
             if gears.len() == 2 {
-                gear_ratios.push(gears.iter().map(|(n,_,_)| n).product());
+                let ratio = gears
+                    .iter()
+                    .try_fold(1u64, |product, (n, _, _)| product.checked_mul(*n))
+                    .ok_or(SchematicError::Overflow)?;
+                gear_ratios.push(ratio);
             }
         }
     }
 
     // numbers.iter().filter(|(_,v)| **v == 0).map(|(n, _)| n).sum()
-    gear_ratios.iter().sum()
+    gear_ratios
+        .iter()
+        .try_fold(0u64, |sum, ratio| sum.checked_add(*ratio))
+        .ok_or(SchematicError::Overflow)
 }
 
 #[cfg(test)]
@@ -104,6 +100,26 @@ mod tests {
     #[test]
     fn test_part2() {
         let input = include_str!("test2.txt");
-        assert_eq!(part2(input), 467835);
+        assert_eq!(part2(input), Ok(467835));
+    }
+
+    #[test]
+    fn test_part2_overflow() {
+        let input = concat!(
+            "..999.\n",
+            "....*.\n",
+            "...999"
+        );
+        assert_eq!(part2(input), Ok(998001));
+
+        let input = concat!(
+            "..999..\n",
+            "....*..\n",
+            "....10000000000000000000"
+        );
+        assert_eq!(part2(input), Err(SchematicError::Overflow));
+
+        let input = "*99999999999999999999";
+        assert_eq!(part2(input), Err(SchematicError::NumberTooLarge { x: 1, y: 0 }));
     }
 }
@@ -0,0 +1,150 @@
+#[derive(Debug, PartialEq)]
+pub enum Token {
+    Number { x: usize, width: usize, value: u64 },
+    Symbol { x: usize, symbol: char },
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SchematicError {
+    /// The number starting at `(x, y)` has too many digits for a `u64`.
+    NumberTooLarge { x: usize, y: usize },
+    /// A sum or gear ratio does not fit in a `u64`.
+    Overflow,
+}
+
+impl std::fmt::Display for SchematicError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchematicError::NumberTooLarge { x, y } => {
+                write!(f, "number at ({}, {}) does not fit in 64 bits", x, y)
+            }
+            SchematicError::Overflow => write!(f, "result does not fit in 64 bits"),
+        }
+    }
+}
+
+/// Walks the bytes of a single schematic line once, yielding every number span
+/// and symbol position without allocating.
+pub struct Scanner<'a> {
+    line: &'a str,
+    y: usize,
+    position: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(line: &'a str) -> Self {
+        Self::on_line(line, 0)
+    }
+
+    /// A scanner for the line at row `y`, so errors can say where they are.
+    pub fn on_line(line: &'a str, y: usize) -> Self {
+        Self { line, y, position: 0 }
+    }
+}
+
+impl Iterator for Scanner<'_> {
+    type Item = Result<Token, SchematicError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.line.as_bytes();
+
+        while let Some(byte) = bytes.get(self.position) {
+            let x = self.position;
+
+            match byte {
+                b'.' => self.position += 1,
+                b'0'..=b'9' => {
+                    let mut value = Some(0u64);
+                    while let Some(digit @ b'0'..=b'9') = bytes.get(self.position) {
+                        value = value
+                            .and_then(|v| v.checked_mul(10))
+                            .and_then(|v| v.checked_add((digit - b'0') as u64));
+                        self.position += 1;
+                    }
+
+                    let width = self.position - x;
+                    return Some(match value {
+                        Some(value) => Ok(Token::Number { x, width, value }),
+                        None => Err(SchematicError::NumberTooLarge { x, y: self.y }),
+                    });
+                }
+                _ => {
+                    let symbol = self.line[x..].chars().next().unwrap();
+                    self.position += symbol.len_utf8();
+
+                    return Some(Ok(Token::Symbol { x, symbol }));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Scans every line of a schematic, pairing each token with its row.
+pub fn scan(input: &str) -> impl Iterator<Item = Result<(usize, Token), SchematicError>> + '_ {
+    input.split('\n').enumerate().flat_map(|(y, line)| {
+        Scanner::on_line(line, y).map(move |token| token.map(|token| (y, token)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scanner() {
+        assert_eq!(
+            Scanner::new("467..114.*").collect::<Vec<_>>(),
+            vec![
+                Ok(Token::Number { x: 0, width: 3, value: 467 }),
+                Ok(Token::Number { x: 5, width: 3, value: 114 }),
+                Ok(Token::Symbol { x: 9, symbol: '*' }),
+            ]
+        );
+
+        assert_eq!(
+            Scanner::new("617*#.58").collect::<Vec<_>>(),
+            vec![
+                Ok(Token::Number { x: 0, width: 3, value: 617 }),
+                Ok(Token::Symbol { x: 3, symbol: '*' }),
+                Ok(Token::Symbol { x: 4, symbol: '#' }),
+                Ok(Token::Number { x: 6, width: 2, value: 58 }),
+            ]
+        );
+
+        assert_eq!(Scanner::new("..........").count(), 0);
+        assert_eq!(Scanner::new("").count(), 0);
+    }
+
+    #[test]
+    fn test_scanner_long_numbers() {
+        assert_eq!(
+            Scanner::new("1234567890123*18446744073709551615").collect::<Vec<_>>(),
+            vec![
+                Ok(Token::Number { x: 0, width: 13, value: 1234567890123 }),
+                Ok(Token::Symbol { x: 13, symbol: '*' }),
+                Ok(Token::Number { x: 14, width: 20, value: u64::MAX }),
+            ]
+        );
+
+        assert_eq!(
+            Scanner::on_line("..18446744073709551616.7", 4).collect::<Vec<_>>(),
+            vec![
+                Err(SchematicError::NumberTooLarge { x: 2, y: 4 }),
+                Ok(Token::Number { x: 23, width: 1, value: 7 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan() {
+        assert_eq!(
+            scan("1.\n.*").collect::<Vec<_>>(),
+            vec![
+                Ok((0, Token::Number { x: 0, width: 1, value: 1 })),
+                Ok((1, Token::Symbol { x: 1, symbol: '*' })),
+            ]
+        );
+    }
+}
@@ -0,0 +1,92 @@
+use std::{collections::HashSet, time::Instant};
+
+use day_04::{to_lines, Card};
+
+/// Times parsing and matching a large batch of random cards, first with the
+/// bitset `NumberSet` and then with the `HashSet`s the puzzle solutions used
+/// to. Takes the number of cards as an optional argument.
+fn main() {
+    let count = std::env::args()
+        .nth(1)
+        .map(|s| s.parse().unwrap())
+        .unwrap_or(1_000_000);
+
+    let input = generate_cards(count, 0x2545f4914f6cdd1d);
+    let lines = to_lines(&input);
+
+    let start = Instant::now();
+    let matches = lines
+        .iter()
+        .map(|l| l.parse::<Card>().unwrap().matches())
+        .sum::<usize>();
+    report("NumberSet", count, start);
+
+    let start = Instant::now();
+    let hash_matches = lines.iter().map(|l| hash_matches(l)).sum::<usize>();
+    report("HashSet", count, start);
+
+    assert_eq!(matches, hash_matches);
+    println!("Matches: {}", matches);
+}
+
+fn report(name: &str, count: usize, start: Instant) {
+    let elapsed = start.elapsed();
+    println!(
+        "{:<9} {} cards in {:.1} ms ({:.0} cards/s)",
+        name,
+        count,
+        elapsed.as_secs_f64() * 1000.0,
+        count as f64 / elapsed.as_secs_f64()
+    );
+}
+
+/// Random cards in the puzzle's format, 10 playing numbers and 25 winning
+/// numbers each below 100, from a xorshift generator so every run matches the
+/// same cards.
+fn generate_cards(count: usize, seed: u64) -> String {
+    let mut state = seed;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    let mut numbers = |n: usize| {
+        (0..n)
+            .map(|_| format!("{:>2}", next() % 100))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    (1..=count)
+        .map(|number| format!("Card {}: {} | {}", number, numbers(10), numbers(25)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Parses and matches a card the way the puzzle solutions first did.
+fn hash_matches(line: &str) -> usize {
+    let parts = line.split(": ").collect::<Vec<_>>();
+    let numbers: Vec<HashSet<usize>> = parts[1]
+        .split(" | ")
+        .map(|part| part.split_whitespace().flat_map(|s| s.parse::<usize>()).collect())
+        .collect();
+
+    let (playing, winning) = (numbers[0].clone(), numbers[1].clone());
+    playing.intersection(&winning).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_agree() {
+        let input = [include_str!("test1.txt"), &generate_cards(100, 1)].join("\n");
+
+        for line in to_lines(&input) {
+            assert_eq!(line.parse::<Card>().unwrap().matches(), hash_matches(line));
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use day_04::{to_lines, validate_deck, Card, CopyCascade, DeckError, Scoring};
+
+fn main() {
+    let input = include_str!("input.txt");
+    let dot = std::env::args().any(|arg| arg == "--dot");
+
+    let cards = to_lines(input)
+        .iter()
+        .map(|l| l.parse::<Card>().unwrap())
+        .collect::<Vec<_>>();
+
+    let cascade = match validate_deck(&cards).and_then(|_| Cascade::new(&cards)) {
+        Ok(cascade) => cascade,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if dot {
+        print!("{}", cascade.to_dot());
+    } else {
+        print!("{}", cascade.trace());
+    }
+}
+
+/// Copies of card `to` won by every instance of card `from`.
+#[derive(Debug, PartialEq)]
+struct Grant {
+    from: usize,
+    to: usize,
+    copies: u128,
+}
+
+#[derive(Debug, PartialEq)]
+struct Cascade {
+    numbers: Vec<usize>,
+    results: Vec<u128>,
+    grants: Vec<Grant>,
+    total: u128,
+}
+
+impl Cascade {
+    /// Follows the puzzle's part 2 cascade, recording which cards every copy
+    /// came from.
+    fn new(cards: &[Card]) -> Result<Self, DeckError> {
+        let rule = CopyCascade { fan_out: 1 };
+        let results = rule.copies(cards)?;
+        let total = rule.score(cards)?;
+
+        let grants = cards
+            .iter()
+            .enumerate()
+            .flat_map(|(position, card)| {
+                let copies = results[position];
+                rule.won(cards, position).map(move |i| Grant {
+                    from: card.number,
+                    to: cards[i].number,
+                    copies,
+                })
+            })
+            .collect();
+
+        Ok(Self {
+            numbers: cards.iter().map(|c| c.number).collect(),
+            results,
+            grants,
+            total,
+        })
+    }
+
+    fn trace(&self) -> String {
+        let mut out = String::new();
+
+        for (number, count) in self.numbers.iter().zip(self.results.iter()) {
+            let sources = self
+                .grants
+                .iter()
+                .filter(|g| g.to == *number)
+                .map(|g| format!("card {} (+{})", g.from, g.copies))
+                .collect::<Vec<_>>();
+
+            out.push_str(&format!("Card {}: {}", number, count));
+            if !sources.is_empty() {
+                out.push_str(&format!(" <- {}", sources.join(", ")));
+            }
+            out.push('\n');
+        }
+
+        out.push_str(&format!("Total: {}\n", self.total));
+        out
+    }
+
+    fn to_dot(&self) -> String {
+        let mut out = String::from("digraph cascade {\n");
+
+        for (number, count) in self.numbers.iter().zip(self.results.iter()) {
+            out.push_str(&format!(
+                "    {} [label=\"Card {}\\n{}\"];\n",
+                number, number, count
+            ));
+        }
+
+        for grant in self.grants.iter() {
+            out.push_str(&format!(
+                "    {} -> {} [label=\"{}\"];\n",
+                grant.from, grant.to, grant.copies
+            ));
+        }
+
+        out.push_str("}\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cascade() {
+        let input = include_str!("test1.txt");
+        let cards = to_lines(input)
+            .iter()
+            .map(|l| l.parse::<Card>().unwrap())
+            .collect::<Vec<_>>();
+        let cascade = Cascade::new(&cards).unwrap();
+
+        assert_eq!(cascade.results, vec![1, 2, 4, 8, 14, 1]);
+        assert_eq!(cascade.total, 30);
+        assert_eq!(cascade.grants.len(), 4 + 2 + 2 + 1);
+        assert_eq!(
+            cascade.grants[4..6],
+            [
+                Grant { from: 2, to: 3, copies: 2 },
+                Grant { from: 2, to: 4, copies: 2 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trace() {
+        let input = "Card 1: 1 2 | 1 2\nCard 2: 1 | 1\nCard 3: 1 | 2";
+        let cards = to_lines(input)
+            .iter()
+            .map(|l| l.parse::<Card>().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            Cascade::new(&cards).unwrap().trace(),
+            concat!(
+                "Card 1: 1\n",
+                "Card 2: 2 <- card 1 (+1)\n",
+                "Card 3: 4 <- card 1 (+1), card 2 (+2)\n",
+                "Total: 7\n"
+            )
+        );
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let input = "Card 1: 1 | 1\nCard 2: 1 | 2";
+        let cards = to_lines(input)
+            .iter()
+            .map(|l| l.parse::<Card>().unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            Cascade::new(&cards).unwrap().to_dot(),
+            concat!(
+                "digraph cascade {\n",
+                "    1 [label=\"Card 1\\n1\"];\n",
+                "    2 [label=\"Card 2\\n2\"];\n",
+                "    1 -> 2 [label=\"1\"];\n",
+                "}\n"
+            )
+        );
+    }
+}
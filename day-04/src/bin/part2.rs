@@ -1,125 +1,91 @@
-use core::str::FromStr;
-use std::{collections::HashSet, fmt::Error};
+use day_04::{to_lines, validate_deck, Card, CopyCascade, DeckError, Scoring};
 
 fn main() {
     let input = include_str!("input.txt");
-    let result = part2(input);
 
-    println!("Result: {}", result);
-}
-
-fn to_lines(input: &str) -> Vec<&str> {
-    input.split("\n").collect()
-}
-
-#[derive(Debug, PartialEq)]
-struct Card {
-    number: usize,
-    playing_numbers: HashSet<usize>,
-    winning_numbers: HashSet<usize>,
-}
-
-impl Card {
-    fn new(
-        number: usize,
-        playing_numbers: HashSet<usize>,
-        winning_numbers: HashSet<usize>,
-    ) -> Self {
-        Self {
-            number,
-            playing_numbers,
-            winning_numbers,
+    match part2(input) {
+        Ok(result) => println!("Result: {}", result),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
         }
     }
-
-    fn matches(&self) -> usize {
-        self.playing_numbers
-            .intersection(&self.winning_numbers)
-            .count()
-    }
 }
 
-impl FromStr for Card {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts = s.split(": ").collect::<Vec<_>>();
-        let card_number: usize = parts[0]
-            .trim()
-            .strip_prefix("Card")
-            .and_then(|s| s.trim().parse().ok())
-            .unwrap();
-
-        let numbers: Vec<HashSet<usize>> = parts[1]
-            .split(" | ")
-            .map(|part| {
-                part.split_whitespace()
-                    .flat_map(|s| s.parse::<usize>())
-                    .collect()
-            })
-            .collect();
-
-        Ok(Card::new(
-            card_number,
-            numbers[0].clone(),
-            numbers[1].clone(),
-        ))
-    }
-}
-
-fn part2(input: &str) -> usize {
+fn part2(input: &str) -> Result<u128, DeckError> {
     let lines = to_lines(input);
     let cards = lines
         .iter()
         .map(|l| l.parse::<Card>().unwrap())
         .collect::<Vec<_>>();
-    let mut results: Vec<usize> = vec![1; cards.len()];
+    validate_deck(&cards)?;
 
-    for card in cards.iter() {
-        let cards_won = card.number..(card.number + card.matches());
-        for i in cards_won {
-            results[i] += results[card.number - 1];
-        }
-    }
-
-    results.iter().sum()
+    Ok(CopyCascade { fan_out: 1 }.score(&cards)?)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// A deck where every card wins a copy of every card after it, so the
+    /// copy counts double from one card to the next.
+    fn doubling_deck(size: usize) -> String {
+        (1..=size)
+            .map(|number| {
+                let numbers = (1..=(size - number))
+                    .map(|n| n.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                format!("Card {}: {} | {}", number, numbers, numbers)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     #[test]
-    fn test_to_lines() {
-        let input = "A\nB\nC";
-        assert_eq!(to_lines(input), ["A", "B", "C"]);
+    fn test_part2() {
+        let input = include_str!("test1.txt");
+
+        assert_eq!(part2(input), Ok(30));
     }
 
     #[test]
-    fn test_parse_card() {
+    fn test_part2_overflow() {
+        assert_eq!(part2(&doubling_deck(3)), Ok(7));
+        assert_eq!(part2(&doubling_deck(100)), Ok((1 << 100) - 1));
+        assert_eq!(part2(&doubling_deck(128)), Ok(u128::MAX));
         assert_eq!(
-            "Card 1: 1 2 3 | 4 5 6".parse(),
-            Ok(Card {
-                number: 1,
-                playing_numbers: HashSet::from([1, 2, 3]),
-                winning_numbers: HashSet::from([4, 5, 6]),
-            })
-        )
+            part2(&doubling_deck(129)),
+            Err(DeckError::Overflow { card: 129 })
+        );
     }
 
     #[test]
-    fn test_card_matches() {
-        let card = Card::new(1, HashSet::from([1, 2, 3]), HashSet::from([2, 3, 4]));
-        assert_eq!(card.matches(), 2);
+    fn test_part2_winnings_past_end() {
+        let input = "Card 1: 1 2 | 1 2\nCard 2: 1 2 3 | 1 2 3";
 
-        let card = Card::new(2, HashSet::from([1, 2, 3]), HashSet::from([4, 5, 6]));
-        assert_eq!(card.matches(), 0);
+        assert_eq!(part2(input), Ok(3));
     }
 
     #[test]
-    fn test_part2() {
-        let input = include_str!("test1.txt");
+    fn test_part2_numbering() {
+        let input = "Card 5: 1 | 1\nCard 6: 1 | 2";
+        assert_eq!(part2(input), Ok(3));
 
-        assert_eq!(part2(input), 30);
+        let input = "Card 1: 1 | 1\nCard 3: 1 | 2";
+        assert_eq!(
+            part2(input),
+            Err(DeckError::MissingCard {
+                expected: 2,
+                found: 3,
+                line: 2
+            })
+        );
+
+        let input = "Card 1: 1 | 1\nCard 2: 1 | 2\nCard 2: 1 | 2";
+        assert_eq!(
+            part2(input),
+            Err(DeckError::DuplicateCard { number: 2, line: 3 })
+        );
     }
 }
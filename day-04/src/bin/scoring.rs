@@ -0,0 +1,192 @@
+use day_04::{
+    to_lines, validate_deck, Card, CopyCascade, DeckError, Doubling, Fibonacci, Linear, Overflow,
+    Scoring,
+};
+
+fn main() {
+    let input = include_str!("input.txt");
+    let cards = read_deck(input).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let rules: Vec<Box<dyn Scoring>> = if args.is_empty() {
+        default_rules()
+    } else {
+        args.iter()
+            .map(|arg| {
+                parse_rule(arg).unwrap_or_else(|| {
+                    eprintln!("Unknown scoring rule: {}", arg);
+                    std::process::exit(1);
+                })
+            })
+            .collect()
+    };
+
+    for (name, score) in score_all(&rules, &cards) {
+        match score {
+            Ok(score) => println!("{:<12} {}", name, score),
+            Err(e) => println!("{:<12} {}", name, e),
+        }
+    }
+}
+
+/// Parses the deck and checks its numbering, since the cascade rules count
+/// cards by their position.
+fn read_deck(input: &str) -> Result<Vec<Card>, DeckError> {
+    let cards = to_lines(input)
+        .iter()
+        .map(|l| l.parse::<Card>().unwrap())
+        .collect::<Vec<_>>();
+    validate_deck(&cards)?;
+
+    Ok(cards)
+}
+
+fn default_rules() -> Vec<Box<dyn Scoring>> {
+    vec![
+        Box::new(Doubling),
+        Box::new(Linear),
+        Box::new(Fibonacci),
+        Box::new(CopyCascade { fan_out: 1 }),
+    ]
+}
+
+/// Reads a rule name as printed by `Scoring::name`, e.g. `doubling` or
+/// `cascade:2`. A bare `cascade` uses a fan-out of 1.
+fn parse_rule(s: &str) -> Option<Box<dyn Scoring>> {
+    match s.split_once(':') {
+        None => match s {
+            "doubling" => Some(Box::new(Doubling)),
+            "linear" => Some(Box::new(Linear)),
+            "fibonacci" => Some(Box::new(Fibonacci)),
+            "cascade" => Some(Box::new(CopyCascade { fan_out: 1 })),
+            _ => None,
+        },
+        Some(("cascade", fan_out)) => fan_out
+            .parse()
+            .ok()
+            .map(|fan_out| Box::new(CopyCascade { fan_out }) as Box<dyn Scoring>),
+        Some(_) => None,
+    }
+}
+
+fn score_all(rules: &[Box<dyn Scoring>], cards: &[Card]) -> Vec<(String, Result<u128, Overflow>)> {
+    rules
+        .iter()
+        .map(|rule| (rule.name(), rule.score(cards)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_cards() -> Vec<Card> {
+        read_deck(include_str!("test1.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_doubling() {
+        assert_eq!(Doubling.score(&test_cards()), Ok(13));
+    }
+
+    #[test]
+    fn test_linear() {
+        assert_eq!(Linear.score(&test_cards()), Ok(4 + 2 + 2 + 1));
+    }
+
+    #[test]
+    fn test_fibonacci() {
+        assert_eq!(Fibonacci.score(&test_cards()), Ok(5 + 2 + 2 + 1));
+    }
+
+    #[test]
+    fn test_copy_cascade() {
+        assert_eq!(CopyCascade { fan_out: 1 }.score(&test_cards()), Ok(30));
+        assert_eq!(
+            CopyCascade { fan_out: 2 }.score(&test_cards()),
+            Ok(1 + 2 + 4 + 8 + 16 + 16)
+        );
+    }
+
+    #[test]
+    fn test_overflow() {
+        let numbers = (1..=200)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let cards = read_deck(&format!("Card 1: {} | {}", numbers, numbers)).unwrap();
+
+        assert_eq!(Doubling.score(&cards), Err(Overflow { card: 1 }));
+        assert_eq!(Linear.score(&cards), Ok(200));
+        assert_eq!(Fibonacci.score(&cards), Err(Overflow { card: 1 }));
+        assert_eq!(CopyCascade { fan_out: 1 }.score(&cards), Ok(1));
+
+        let deck = (1..=130)
+            .map(|number| format!("Card {}: {} | {}", number, numbers, numbers))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let cards = read_deck(&deck).unwrap();
+        assert_eq!(
+            CopyCascade { fan_out: 1 }.score(&cards),
+            Err(Overflow { card: 129 })
+        );
+        assert_eq!(
+            CopyCascade { fan_out: 1 }.score(&cards[..100]),
+            Ok((1 << 100) - 1)
+        );
+    }
+
+    #[test]
+    fn test_read_deck() {
+        assert_eq!(
+            read_deck("Card 3: 1 | 1\nCard 4: 1 | 2").map(|c| c.len()),
+            Ok(2)
+        );
+        assert_eq!(
+            read_deck("Card 1: 1 | 1\nCard 1: 1 | 2").map(|c| c.len()),
+            Err(DeckError::DuplicateCard { number: 1, line: 2 })
+        );
+        assert_eq!(
+            read_deck("Card 1: 1 | 1\nCard 3: 1 | 2").map(|c| c.len()),
+            Err(DeckError::MissingCard {
+                expected: 2,
+                found: 3,
+                line: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_rule() {
+        assert_eq!(
+            parse_rule("doubling").map(|r| r.name()),
+            Some("doubling".to_string())
+        );
+        assert_eq!(
+            parse_rule("cascade").map(|r| r.name()),
+            Some("cascade:1".to_string())
+        );
+        assert_eq!(
+            parse_rule("cascade:3").map(|r| r.name()),
+            Some("cascade:3".to_string())
+        );
+        assert!(parse_rule("cascade:x").is_none());
+        assert!(parse_rule("squares").is_none());
+    }
+
+    #[test]
+    fn test_score_all() {
+        assert_eq!(
+            score_all(&default_rules(), &test_cards()),
+            vec![
+                ("doubling".to_string(), Ok(13)),
+                ("linear".to_string(), Ok(9)),
+                ("fibonacci".to_string(), Ok(10)),
+                ("cascade:1".to_string(), Ok(30)),
+            ]
+        );
+    }
+}
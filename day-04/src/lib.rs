@@ -0,0 +1,362 @@
+use core::str::FromStr;
+use std::{collections::HashSet, fmt::Error, ops::Range};
+
+pub fn to_lines(input: &str) -> Vec<&str> {
+    input.split("\n").collect()
+}
+
+/// A set of card numbers. Numbers below 128 are kept in a bitset so matches
+/// can be counted with a single popcount; anything larger falls back to a
+/// sorted vector.
+#[derive(Debug, PartialEq, Default)]
+pub struct NumberSet {
+    bits: u128,
+    large: Vec<usize>,
+}
+
+impl NumberSet {
+    pub fn intersection_count(&self, other: &Self) -> usize {
+        let mut count = (self.bits & other.bits).count_ones() as usize;
+
+        let (mut a, mut b) = (0, 0);
+        while a < self.large.len() && b < other.large.len() {
+            match self.large[a].cmp(&other.large[b]) {
+                std::cmp::Ordering::Less => a += 1,
+                std::cmp::Ordering::Greater => b += 1,
+                std::cmp::Ordering::Equal => {
+                    count += 1;
+                    a += 1;
+                    b += 1;
+                }
+            }
+        }
+
+        count
+    }
+}
+
+impl FromIterator<usize> for NumberSet {
+    fn from_iter<T: IntoIterator<Item = usize>>(iter: T) -> Self {
+        let mut set = NumberSet::default();
+
+        for n in iter {
+            if n < 128 {
+                set.bits |= 1 << n;
+            } else {
+                set.large.push(n);
+            }
+        }
+
+        set.large.sort_unstable();
+        set.large.dedup();
+        set
+    }
+}
+
+impl<const N: usize> From<[usize; N]> for NumberSet {
+    fn from(numbers: [usize; N]) -> Self {
+        numbers.into_iter().collect()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Card {
+    pub number: usize,
+    pub playing_numbers: NumberSet,
+    pub winning_numbers: NumberSet,
+}
+
+impl Card {
+    pub fn new(number: usize, playing_numbers: NumberSet, winning_numbers: NumberSet) -> Self {
+        Self {
+            number,
+            playing_numbers,
+            winning_numbers,
+        }
+    }
+
+    pub fn matches(&self) -> usize {
+        self.playing_numbers
+            .intersection_count(&self.winning_numbers)
+    }
+}
+
+impl FromStr for Card {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s.split(": ").collect::<Vec<_>>();
+        let card_number: usize = parts[0]
+            .trim()
+            .strip_prefix("Card")
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap();
+
+        let mut numbers = parts[1].split(" | ").map(|part| {
+            part.split_whitespace()
+                .flat_map(|s| s.parse::<usize>())
+                .collect()
+        });
+
+        Ok(Card::new(
+            card_number,
+            numbers.next().unwrap(),
+            numbers.next().unwrap(),
+        ))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DeckError {
+    DuplicateCard { number: usize, line: usize },
+    MissingCard { expected: usize, found: usize, line: usize },
+    Overflow { card: usize },
+}
+
+impl std::fmt::Display for DeckError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeckError::DuplicateCard { number, line } => {
+                write!(f, "card {} appears again on line {}", number, line)
+            }
+            DeckError::MissingCard {
+                expected,
+                found,
+                line,
+            } => write!(
+                f,
+                "expected card {} on line {} but found card {}",
+                expected, line, found
+            ),
+            DeckError::Overflow { card } => {
+                write!(f, "copy count overflowed at card {}", card)
+            }
+        }
+    }
+}
+
+/// Checks that the cards are numbered contiguously in the order they appear,
+/// so that a card's position in the deck can stand in for its number.
+pub fn validate_deck(cards: &[Card]) -> Result<(), DeckError> {
+    let mut seen = HashSet::new();
+
+    for (position, card) in cards.iter().enumerate() {
+        let line = position + 1;
+
+        if !seen.insert(card.number) {
+            return Err(DeckError::DuplicateCard {
+                number: card.number,
+                line,
+            });
+        }
+
+        let expected = cards[0].number + position;
+        if card.number != expected {
+            return Err(DeckError::MissingCard {
+                expected,
+                found: card.number,
+                line,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// The card at which a score stopped fitting in a `u128`.
+#[derive(Debug, PartialEq)]
+pub struct Overflow {
+    pub card: usize,
+}
+
+impl std::fmt::Display for Overflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "score overflowed at card {}", self.card)
+    }
+}
+
+impl From<Overflow> for DeckError {
+    fn from(overflow: Overflow) -> Self {
+        DeckError::Overflow {
+            card: overflow.card,
+        }
+    }
+}
+
+pub trait Scoring {
+    fn name(&self) -> String;
+
+    /// The score for the whole deck, or the card where it stopped fitting in a
+    /// `u128`.
+    fn score(&self, cards: &[Card]) -> Result<u128, Overflow>;
+}
+
+/// Adds up a per-card score, stopping at the first overflow.
+fn sum_cards(cards: &[Card], value: impl Fn(usize) -> Option<u128>) -> Result<u128, Overflow> {
+    cards.iter().try_fold(0u128, |total, c| {
+        value(c.matches())
+            .and_then(|value| total.checked_add(value))
+            .ok_or(Overflow { card: c.number })
+    })
+}
+
+/// Each card scores one point for its first match and doubles for every match
+/// after that. This is the puzzle's part 1 rule.
+pub struct Doubling;
+
+impl Scoring for Doubling {
+    fn name(&self) -> String {
+        "doubling".to_string()
+    }
+
+    fn score(&self, cards: &[Card]) -> Result<u128, Overflow> {
+        sum_cards(cards, |matches| match matches {
+            0 => Some(0),
+            matches => 1u128.checked_shl(matches as u32 - 1),
+        })
+    }
+}
+
+/// Each card scores one point per match.
+pub struct Linear;
+
+impl Scoring for Linear {
+    fn name(&self) -> String {
+        "linear".to_string()
+    }
+
+    fn score(&self, cards: &[Card]) -> Result<u128, Overflow> {
+        sum_cards(cards, |matches| Some(matches as u128))
+    }
+}
+
+/// Each card scores along the Fibonacci sequence: 1, 2, 3, 5, 8, ... points
+/// for 1, 2, 3, 4, 5, ... matches.
+pub struct Fibonacci;
+
+impl Scoring for Fibonacci {
+    fn name(&self) -> String {
+        "fibonacci".to_string()
+    }
+
+    fn score(&self, cards: &[Card]) -> Result<u128, Overflow> {
+        sum_cards(cards, |matches| match matches {
+            0 => Some(0),
+            matches => {
+                let (mut a, mut b) = (0u128, 1u128);
+                for _ in 1..matches {
+                    (a, b) = (b, a.checked_add(b)?);
+                }
+                a.checked_add(b)
+            }
+        })
+    }
+}
+
+/// Every match wins a copy of the next `fan_out` cards, and the score is the
+/// total number of cards held at the end. A fan-out of 1 is the puzzle's
+/// part 2 rule.
+pub struct CopyCascade {
+    pub fan_out: usize,
+}
+
+impl CopyCascade {
+    /// The positions of the cards that every copy of the card at `position`
+    /// wins. Winnings that would run past the last card are dropped.
+    pub fn won(&self, cards: &[Card], position: usize) -> Range<usize> {
+        let won = cards[position].matches().saturating_mul(self.fan_out);
+        (position + 1)..(position + 1).saturating_add(won).min(cards.len())
+    }
+
+    /// How many copies of every card, by position, are held at the end.
+    pub fn copies(&self, cards: &[Card]) -> Result<Vec<u128>, Overflow> {
+        let mut results: Vec<u128> = vec![1; cards.len()];
+
+        for position in 0..cards.len() {
+            for i in self.won(cards, position) {
+                results[i] = results[i].checked_add(results[position]).ok_or(Overflow {
+                    card: cards[i].number,
+                })?;
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+impl Scoring for CopyCascade {
+    fn name(&self) -> String {
+        format!("cascade:{}", self.fan_out)
+    }
+
+    fn score(&self, cards: &[Card]) -> Result<u128, Overflow> {
+        self.copies(cards)?
+            .iter()
+            .zip(cards.iter())
+            .try_fold(0u128, |total, (count, card)| {
+                total
+                    .checked_add(*count)
+                    .ok_or(Overflow { card: card.number })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_lines() {
+        let input = "A\nB\nC";
+        assert_eq!(to_lines(input), ["A", "B", "C"]);
+    }
+
+    #[test]
+    fn test_number_set() {
+        let a = NumberSet::from([1, 2, 3, 127, 128, 500, 1000]);
+        let b = NumberSet::from([3, 4, 127, 500, 999, 1000]);
+        assert_eq!(a.intersection_count(&b), 4);
+        assert_eq!(b.intersection_count(&a), 4);
+
+        assert_eq!(NumberSet::from([200, 5, 200, 150]).large, vec![150, 200]);
+        assert_eq!(NumberSet::from([]).intersection_count(&a), 0);
+    }
+
+    #[test]
+    fn test_parse_card() {
+        assert_eq!(
+            "Card 1: 1 2 3 | 4 5 6".parse(),
+            Ok(Card {
+                number: 1,
+                playing_numbers: NumberSet::from([1, 2, 3]),
+                winning_numbers: NumberSet::from([4, 5, 6]),
+            })
+        )
+    }
+
+    #[test]
+    fn test_card_matches() {
+        let card = Card::new(1, NumberSet::from([1, 2, 3]), NumberSet::from([2, 3, 4]));
+        assert_eq!(card.matches(), 2);
+
+        let card = Card::new(2, NumberSet::from([1, 2, 3]), NumberSet::from([4, 5, 6]));
+        assert_eq!(card.matches(), 0);
+    }
+
+    #[test]
+    fn test_copy_cascade() {
+        let cards = to_lines(include_str!("bin/test1.txt"))
+            .iter()
+            .map(|l| l.parse::<Card>().unwrap())
+            .collect::<Vec<_>>();
+
+        let rule = CopyCascade { fan_out: 1 };
+        assert_eq!(rule.won(&cards, 0), 1..5);
+        assert_eq!(rule.won(&cards, 5), 6..6);
+        assert_eq!(rule.copies(&cards), Ok(vec![1, 2, 4, 8, 14, 1]));
+        assert_eq!(rule.score(&cards), Ok(30));
+
+        let rule = CopyCascade { fan_out: 2 };
+        assert_eq!(rule.won(&cards, 2), 3..6);
+    }
+}
@@ -0,0 +1,83 @@
+use day_05::{pipeline, read_map, OverlapError, PiecewiseMap};
+
+fn main() {
+    let input = include_str!("input.txt");
+    let seed_to_location = compose(input).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+
+    let seeds = std::env::args()
+        .skip(1)
+        .map(|s| s.parse::<usize>().unwrap())
+        .collect::<Vec<_>>();
+
+    if seeds.is_empty() {
+        println!("seed-to-location map:");
+        print!("{}", seed_to_location);
+    } else {
+        for seed in seeds {
+            println!("{} -> {}", seed, seed_to_location.lookup(seed));
+        }
+    }
+}
+
+/// Composes all seven almanac maps into a single seed-to-location map,
+/// failing on the first map with overlapping lines.
+fn compose(input: &str) -> Result<PiecewiseMap, OverlapError> {
+    let seed_to_soil = read_map(input, "seed-to-soil");
+    let soil_to_fertilizer = read_map(input, "soil-to-fertilizer");
+    let fertilizer_to_water = read_map(input, "fertilizer-to-water");
+    let water_to_light = read_map(input, "water-to-light");
+    let light_to_temperature = read_map(input, "light-to-temperature");
+    let temperature_to_humidity = read_map(input, "temperature-to-humidity");
+    let humidity_to_location = read_map(input, "humidity-to-location");
+
+    pipeline!(seed_to_soil
+        => soil_to_fertilizer
+        => fertilizer_to_water
+        => water_to_light
+        => light_to_temperature
+        => temperature_to_humidity
+        => humidity_to_location)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day_05::Range;
+
+    #[test]
+    fn test_compose() {
+        let input = include_str!("test1.txt");
+        let composed = compose(input).unwrap();
+
+        assert_eq!(composed.lookup(79), 82);
+        assert_eq!(composed.lookup(14), 43);
+        assert_eq!(composed.lookup(55), 86);
+        assert_eq!(composed.lookup(13), 35);
+    }
+
+    #[test]
+    fn test_compose_overlap() {
+        let input = include_str!("test1.txt").replace("50 98 2", "50 97 2");
+
+        assert_eq!(
+            compose(&input),
+            Err(OverlapError {
+                first: Range { start: 50, end: 98 },
+                second: Range { start: 97, end: 99 },
+            })
+        );
+    }
+
+    #[test]
+    fn test_compose_overflow() {
+        let composed = compose("seed-to-soil map:\n0 18446744073709551610 10").unwrap();
+
+        assert_eq!(composed.lookup(18446744073709551610), 0);
+        assert_eq!(composed.lookup(18446744073709551614), 4);
+        assert_eq!(composed.lookup(18446744073709551609), 18446744073709551609);
+        assert_eq!(composed.lookup(5), 5);
+    }
+}
@@ -0,0 +1,227 @@
+use day_05::{read_map, read_seed_ranges, shift, OverlapError, PiecewiseMap, RangeMap, MAPS};
+
+fn main() {
+    let input = include_str!("input.txt");
+    let maps = read_maps(input).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+
+    let locations = std::env::args()
+        .skip(1)
+        .map(|s| s.parse::<usize>().unwrap())
+        .collect::<Vec<_>>();
+
+    if locations.is_empty() {
+        let seed_to_location = maps
+            .iter()
+            .fold(PiecewiseMap::identity(), |acc, map| acc.then(map));
+
+        match lowest_location(&seed_to_location, &read_seed_ranges(input)) {
+            Some((location, seed)) => println!("Lowest location: {} (seed {})", location, seed),
+            None => println!("No seed reaches a location"),
+        }
+        return;
+    }
+
+    for location in locations {
+        println!("location: {}", location);
+
+        let mut ranges = vec![(location, location + 1)];
+        for (name, map) in MAPS.iter().zip(maps.iter()).rev() {
+            ranges = preimage_chain(&[map], &ranges);
+            println!(
+                "{}: {}",
+                name.split("-to-").next().unwrap(),
+                format_ranges(&ranges)
+            );
+        }
+    }
+}
+
+/// Reads every almanac map in order, failing on the first one with
+/// overlapping lines.
+fn read_maps(input: &str) -> Result<Vec<PiecewiseMap>, OverlapError> {
+    MAPS.iter()
+        .map(|name| RangeMap::new(&read_map(input, name)).map(|map| PiecewiseMap::from(&map)))
+        .collect()
+}
+
+/// The ranges of values that `map` sends into `start..end`. Maps need not be
+/// injective, so several ranges can land on the same values.
+fn preimage(map: &PiecewiseMap, start: usize, end: usize) -> Vec<(usize, usize)> {
+    let mut ranges = vec![];
+
+    for segment in map.segments() {
+        let image_start = shift(segment.start, segment.offset);
+        let image_end = image_start.saturating_add(segment.end - segment.start);
+
+        let from = start.max(image_start);
+        let to = end.min(image_end);
+        if from < to {
+            ranges.push((shift(from, -segment.offset), shift(to, -segment.offset)));
+        }
+    }
+
+    merge_ranges(ranges)
+}
+
+/// Sorts ranges and joins any that overlap or touch.
+fn merge_ranges(mut ranges: Vec<(usize, usize)>) -> Vec<(usize, usize)> {
+    ranges.sort_unstable();
+
+    let mut merged: Vec<(usize, usize)> = vec![];
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    merged
+}
+
+/// Walks `ranges` backwards through `maps`, from the last map to the first.
+fn preimage_chain(maps: &[&PiecewiseMap], ranges: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    maps.iter().rev().fold(ranges.to_vec(), |ranges, map| {
+        merge_ranges(
+            ranges
+                .iter()
+                .flat_map(|(start, end)| preimage(map, *start, *end))
+                .collect(),
+        )
+    })
+}
+
+/// Walks the map's segments upwards by location until it finds the lowest
+/// location reached from any of the seed ranges, returning it along with the
+/// seed that gets there.
+fn lowest_location(map: &PiecewiseMap, seed_ranges: &[(usize, usize)]) -> Option<(usize, usize)> {
+    let mut segments = map.segments().to_vec();
+    segments.sort_by_key(|s| shift(s.start, s.offset));
+
+    let mut lowest: Option<(usize, usize)> = None;
+    for segment in segments {
+        if lowest.is_some_and(|(location, _)| shift(segment.start, segment.offset) >= location) {
+            break;
+        }
+
+        for (from, to) in seed_ranges {
+            let seed = segment.start.max(*from);
+            if seed < segment.end.min(*to) {
+                let location = shift(seed, segment.offset);
+                if lowest.is_none_or(|(lowest, _)| location < lowest) {
+                    lowest = Some((location, seed));
+                }
+            }
+        }
+    }
+
+    lowest
+}
+
+fn format_ranges(ranges: &[(usize, usize)]) -> String {
+    ranges
+        .iter()
+        .map(|(start, end)| match end - start {
+            1 => start.to_string(),
+            _ => format!("{}..{}", start, end),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day_05::Range;
+
+    fn test_maps() -> Vec<PiecewiseMap> {
+        read_maps(include_str!("test1.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_preimage() {
+        let map = PiecewiseMap::from(&RangeMap::new(&[(50, 98, 2), (52, 50, 48)]).unwrap());
+
+        assert_eq!(preimage(&map, 81, 82), vec![(79, 80)]);
+        assert_eq!(preimage(&map, 14, 15), vec![(14, 15)]);
+        assert_eq!(preimage(&map, 50, 52), vec![(98, 100)]);
+        // 50 and 51 are reached from both the identity range and the first line
+        assert_eq!(preimage(&map, 48, 54), vec![(48, 52), (98, 100)]);
+        assert_eq!(preimage(&map, 99, 101), vec![(97, 98), (100, 101)]);
+    }
+
+    #[test]
+    fn test_merge_ranges() {
+        assert_eq!(
+            merge_ranges(vec![(5, 10), (0, 2), (2, 4), (8, 12)]),
+            vec![(0, 4), (5, 12)]
+        );
+        assert_eq!(merge_ranges(vec![]), vec![]);
+    }
+
+    #[test]
+    fn test_preimage_chain() {
+        let input = include_str!("test1.txt");
+        let lines = MAPS.map(|name| RangeMap::new(&read_map(input, name)).unwrap());
+        let maps = test_maps();
+        let maps = maps.iter().collect::<Vec<_>>();
+        let composed = maps
+            .iter()
+            .fold(PiecewiseMap::identity(), |acc, map| acc.then(map));
+
+        for location in 0..120 {
+            let seeds = preimage_chain(&maps, &[(location, location + 1)]);
+
+            assert_eq!(seeds, preimage(&composed, location, location + 1));
+            for (start, end) in seeds {
+                for seed in start..end {
+                    let value = lines.iter().fold(seed, |value, map| map.lookup(value));
+                    assert_eq!(value, location);
+                }
+            }
+        }
+
+        assert!(preimage_chain(&maps, &[(82, 83)]).contains(&(79, 80)));
+    }
+
+    #[test]
+    fn test_lowest_location() {
+        let input = include_str!("test1.txt");
+        let composed = test_maps()
+            .iter()
+            .fold(PiecewiseMap::identity(), |acc, map| acc.then(map));
+
+        assert_eq!(
+            lowest_location(&composed, &read_seed_ranges(input)),
+            Some((46, 82))
+        );
+        assert_eq!(lowest_location(&composed, &[(79, 80)]), Some((82, 79)));
+        assert_eq!(lowest_location(&composed, &[]), None);
+    }
+
+    #[test]
+    fn test_read_maps_overlap() {
+        let input = include_str!("test1.txt").replace("50 98 2", "50 97 2");
+
+        assert_eq!(
+            read_maps(&input),
+            Err(OverlapError {
+                first: Range { start: 50, end: 98 },
+                second: Range { start: 97, end: 99 },
+            })
+        );
+    }
+
+    #[test]
+    fn test_preimage_overflow() {
+        let maps = read_maps("seed-to-soil map:\n0 18446744073709551610 10").unwrap();
+        let maps = maps.iter().collect::<Vec<_>>();
+
+        assert_eq!(
+            preimage_chain(&maps, &[(0, 5)]),
+            vec![(0, 5), (18446744073709551610, 18446744073709551615)]
+        );
+    }
+}
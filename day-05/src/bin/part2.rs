@@ -1,58 +1,27 @@
-fn main() {
-    let input = include_str!("input.txt");
-    let result = part2(input);
-
-    println!("Result: {}", result);
-}
-
-fn lookup_map(map: &Vec<(usize, usize, usize)>, value: usize) -> usize {
-    map.iter()
-        .find(|(_, from, size)| value >= *from && value < (from + size))
-        .and_then(|(to, from, _)| Some(to + (value - from)))
-        .unwrap_or(value)
-}
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
 
-fn read_seed_ranges(input: &str) -> Vec<(usize, usize)> {
-    input
-        .split("\n")
-        .nth(0)
-        .unwrap()
-        .strip_prefix("seeds: ")
-        .unwrap()
-        .split(" ")
-        .map(|s| s.parse().unwrap())
-        .collect::<Vec<usize>>()
-        .chunks(2)
-        .map(|chunk| (chunk[0], chunk[0] + chunk[1]))
-        .collect()
-}
+use day_05::{pipeline, read_map, read_seed_ranges, OverlapError, PiecewiseMap};
 
-fn read_map(input: &str, map_name: &str) -> Vec<(usize, usize, usize)> {
-    input
-        .split("\n")
-        .skip_while(|s| !s.starts_with(map_name))
-        .skip(1)
-        .take_while(|l| l.starts_with(|c: char| c.is_numeric()))
-        .map(|l| {
-            let numbers: Vec<usize> = l.split_whitespace().map(|s| s.parse().unwrap()).collect();
-            (numbers[0], numbers[1], numbers[2])
-        })
-        .collect()
-}
+fn main() {
+    let input = include_str!("input.txt");
 
-macro_rules! pipeline {
-    ( $expr:expr => $($funs:tt)=>+ ) => {
-        {
-            let ret = $expr;
-            $(
-                let ret = lookup_map(&$funs, ret);
-            )*
-            ret
+    match part2(input) {
+        Ok(Some(result)) => println!("Result: {}", result),
+        Ok(None) => {
+            eprintln!("Error: no seeds");
+            std::process::exit(1);
         }
-    };
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
 }
 
-fn part2(input: &str) -> usize {
+/// The lowest location for any seed, or `None` if every seed range is empty.
+fn part2(input: &str) -> Result<Option<usize>, OverlapError> {
     let seed_ranges = read_seed_ranges(input);
 
     let seed_to_soil = read_map(input, "seed-to-soil");
@@ -63,79 +32,171 @@ fn part2(input: &str) -> usize {
     let temperature_to_humidity = read_map(input, "temperature-to-humidity");
     let humidity_to_location = read_map(input, "humidity-to-location");
 
+    let seed_to_location = pipeline!(seed_to_soil
+        => soil_to_fertilizer
+        => fertilizer_to_water
+        => water_to_light
+        => light_to_temperature
+        => temperature_to_humidity
+        => humidity_to_location)?;
+
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+
+    Ok(brute_force(&seed_ranges, &seed_to_location, threads))
+}
+
+/// Seeds handed to a worker thread at a time.
+const CHUNK_SIZE: usize = 1 << 20;
+
+/// Splits the seed ranges into chunks of at most `size` seeds.
+fn chunks(seed_ranges: &[(usize, usize)], size: usize) -> Vec<(usize, usize)> {
     seed_ranges
         .iter()
-        .map(|seed_range| {
-            let seeds = (seed_range.0..seed_range.1).collect::<Vec<usize>>();
-
-            // Write some progress to the terminal
-            println!(
-                "SEEDS: {} to {} ({})",
-                seed_range.0,
-                seed_range.1,
-                seed_range.1 - seed_range.0
-            );
+        .flat_map(|(start, end)| {
+            (*start..*end)
+                .step_by(size)
+                .map(move |from| (from, from.saturating_add(size).min(*end)))
+        })
+        .collect()
+}
+
+/// The ETA stays unknown until at least one chunk has finished, since there
+/// is no rate to go on before then.
+fn progress_line(done: usize, total: usize, elapsed: Duration) -> String {
+    let rate = done as f64 / elapsed.as_secs_f64();
+    let eta = match done {
+        0 => "?".to_string(),
+        _ => format!("{:.0}s", (total - done) as f64 / rate),
+    };
 
-            seeds
-                .iter()
-                .map(|seed| {
-                    pipeline!(*seed
-                        => seed_to_soil
-                        => soil_to_fertilizer
-                        => fertilizer_to_water
-                        => water_to_light
-                        => light_to_temperature
-                        => temperature_to_humidity
-                        => humidity_to_location)
+    format!(
+        "{}/{} seeds ({:.1}%), {:.0} seeds/s, ETA {}",
+        done,
+        total,
+        done as f64 * 100.0 / total as f64,
+        rate,
+        eta
+    )
+}
+
+/// Looks up every seed in every range, spreading the work across `threads`
+/// threads and reporting progress on stderr. This is slow, but it makes a
+/// useful cross-check for anything cleverer. Returns `None` if there are no
+/// seeds to look up.
+fn brute_force(
+    seed_ranges: &[(usize, usize)],
+    seed_to_location: &PiecewiseMap,
+    threads: usize,
+) -> Option<usize> {
+    let chunks = chunks(seed_ranges, CHUNK_SIZE);
+    let total = chunks
+        .iter()
+        .map(|(start, end)| end - start)
+        .fold(0, usize::saturating_add);
+
+    let next = AtomicUsize::new(0);
+    let done = AtomicUsize::new(0);
+    let lowest = AtomicUsize::new(usize::MAX);
+
+    thread::scope(|scope| {
+        let workers = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    while let Some((start, end)) = chunks.get(next.fetch_add(1, Ordering::Relaxed))
+                    {
+                        let location = (*start..*end)
+                            .map(|seed| seed_to_location.lookup(seed))
+                            .min()
+                            .unwrap();
+
+                        lowest.fetch_min(location, Ordering::Relaxed);
+                        done.fetch_add(end - start, Ordering::Relaxed);
+                    }
                 })
-                .min()
-                .unwrap()
-        })
-        .min()
-        .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let started = Instant::now();
+        let mut reported = started;
+        while !workers.iter().all(|w| w.is_finished()) {
+            thread::sleep(Duration::from_millis(50));
+
+            if reported.elapsed() >= Duration::from_secs(1) {
+                let done = done.load(Ordering::Relaxed);
+                eprintln!("{}", progress_line(done, total, started.elapsed()));
+                reported = Instant::now();
+            }
+        }
+    });
+
+    (!chunks.is_empty()).then(|| lowest.into_inner())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use day_05::{Range, MAPS};
 
     #[test]
-    fn test_read_seed_ranges() {
-        let input = "seeds: 1 2 3 4\n\nseed-to-soil map:\n 1 2 3 4";
-        assert_eq!(read_seed_ranges(input), vec![(1, 3), (3, 7)]);
-
-        let input = include_str!("test1.txt");
-        assert_eq!(read_seed_ranges(input), vec![(79, 93), (55, 68)]);
+    fn test_chunks() {
+        assert_eq!(
+            chunks(&[(0, 10), (20, 23)], 4),
+            vec![(0, 4), (4, 8), (8, 10), (20, 23)]
+        );
+        assert_eq!(chunks(&[(5, 5)], 4), vec![]);
+        assert_eq!(
+            chunks(&[(usize::MAX - 5, usize::MAX)], 4),
+            vec![
+                (usize::MAX - 5, usize::MAX - 1),
+                (usize::MAX - 1, usize::MAX)
+            ]
+        );
     }
 
     #[test]
-    fn test_read_map() {
-        let input = include_str!("test1.txt");
+    fn test_progress_line() {
         assert_eq!(
-            read_map(input, "seed-to-soil"),
-            vec![(50, 98, 2), (52, 50, 48)]
+            progress_line(250, 1000, Duration::from_secs(5)),
+            "250/1000 seeds (25.0%), 50 seeds/s, ETA 15s"
         );
-
         assert_eq!(
-            read_map(input, "temperature-to-humidity"),
-            vec![(0, 69, 1), (1, 0, 69),]
+            progress_line(0, 1000, Duration::from_secs(5)),
+            "0/1000 seeds (0.0%), 0 seeds/s, ETA ?"
         );
     }
 
     #[test]
-    fn test_lookup_map() {
-        let map = vec![(50, 98, 2), (52, 50, 48)];
-
-        assert_eq!(lookup_map(&map, 79), 81);
-        assert_eq!(lookup_map(&map, 14), 14);
-        assert_eq!(lookup_map(&map, 55), 57);
-        assert_eq!(lookup_map(&map, 13), 13);
+    fn test_brute_force() {
+        let input = include_str!("test1.txt");
+        let maps = MAPS.map(|name| read_map(input, name));
+        let [a, b, c, d, e, f, g] = &maps;
+        let composed = pipeline!(a => b => c => d => e => f => g).unwrap();
+
+        for threads in 1..=4 {
+            assert_eq!(
+                brute_force(&[(79, 93), (55, 68)], &composed, threads),
+                Some(46)
+            );
+            assert_eq!(brute_force(&[(79, 80)], &composed, threads), Some(82));
+            assert_eq!(brute_force(&[(5, 5)], &composed, threads), None);
+            assert_eq!(brute_force(&[], &composed, threads), None);
+        }
     }
 
     #[test]
     fn test_part2() {
         let input = include_str!("test1.txt");
 
-        assert_eq!(part2(input), 46);
+        assert_eq!(part2(input), Ok(Some(46)));
+        assert_eq!(part2(&input.replace("79 14 55 13", "79 0 55 0")), Ok(None));
+
+        let input = input.replace("50 98 2", "50 97 2");
+        assert_eq!(
+            part2(&input),
+            Err(OverlapError {
+                first: Range { start: 50, end: 98 },
+                second: Range { start: 97, end: 99 },
+            })
+        );
     }
 }
@@ -0,0 +1,226 @@
+use day_05::{read_numbered_map, read_seeds, MapLine, OverlapError, RangeMap, MAPS};
+
+fn main() {
+    let input = include_str!("input.txt");
+    let csv = std::env::args().any(|arg| arg == "--csv");
+
+    let mut seeds = std::env::args()
+        .skip(1)
+        .filter(|arg| arg != "--csv")
+        .map(|s| s.parse::<usize>().unwrap())
+        .collect::<Vec<_>>();
+    if seeds.is_empty() {
+        seeds = read_seeds(input);
+    }
+
+    let maps = read_maps(input).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+
+    if csv {
+        println!("seed,stage,value,line");
+    }
+
+    for seed in seeds {
+        let steps = trace(&maps, seed);
+
+        if csv {
+            print!("{}", to_csv(seed, &steps));
+        } else {
+            print!("{}", to_table(seed, &steps));
+        }
+    }
+}
+
+/// A map's numbered lines along with a `RangeMap` over them, which finds the
+/// line covering a value.
+struct NumberedMap {
+    lines: Vec<MapLine>,
+    map: RangeMap,
+}
+
+impl NumberedMap {
+    fn new(lines: Vec<MapLine>) -> Result<Self, OverlapError> {
+        let map = RangeMap::new(
+            &lines
+                .iter()
+                .map(|l| (l.to, l.from, l.size))
+                .collect::<Vec<_>>(),
+        )?;
+
+        Ok(Self { lines, map })
+    }
+
+    /// The line covering `value`, the same one `RangeMap::lookup` uses.
+    fn find_line(&self, value: usize) -> Option<&MapLine> {
+        self.map.line_for(value).map(|i| &self.lines[i])
+    }
+}
+
+/// Reads every almanac map in order, failing on the first one with
+/// overlapping lines.
+fn read_maps(input: &str) -> Result<Vec<NumberedMap>, OverlapError> {
+    MAPS.iter()
+        .map(|name| NumberedMap::new(read_numbered_map(input, name)))
+        .collect()
+}
+
+/// A seed's value after one stage of the almanac, along with the line that
+/// produced it. Identity fallbacks have no line.
+#[derive(Debug, PartialEq)]
+struct Step {
+    stage: &'static str,
+    value: usize,
+    line: Option<MapLine>,
+}
+
+fn trace(maps: &[NumberedMap], seed: usize) -> Vec<Step> {
+    let mut steps = vec![Step {
+        stage: "seed",
+        value: seed,
+        line: None,
+    }];
+
+    for (name, map) in MAPS.iter().zip(maps.iter()) {
+        let value = steps.last().unwrap().value;
+
+        steps.push(Step {
+            stage: name.split("-to-").nth(1).unwrap(),
+            value: map.map.lookup(value),
+            line: map.find_line(value).copied(),
+        });
+    }
+
+    steps
+}
+
+fn to_table(seed: usize, steps: &[Step]) -> String {
+    let mut out = format!("Seed {}\n", seed);
+
+    for (i, step) in steps.iter().enumerate() {
+        let applied = match (i, step.line) {
+            (0, _) => String::new(),
+            (_, Some(l)) => format!("line {} ({} {} {})", l.line, l.to, l.from, l.size),
+            (_, None) => "identity".to_string(),
+        };
+        let row = format!("  {:<12} {:>12}  {}", step.stage, step.value, applied);
+        out.push_str(row.trim_end());
+        out.push('\n');
+    }
+
+    out
+}
+
+fn to_csv(seed: usize, steps: &[Step]) -> String {
+    steps
+        .iter()
+        .skip(1)
+        .map(|step| {
+            let line = step
+                .line
+                .map_or("identity".to_string(), |l| l.line.to_string());
+            format!("{},{},{},{}\n", seed, step.stage, step.value, line)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day_05::Range;
+
+    fn test_maps() -> Vec<NumberedMap> {
+        read_maps(include_str!("test1.txt")).unwrap()
+    }
+
+    #[test]
+    fn test_find_line() {
+        let maps = test_maps();
+
+        assert_eq!(maps[0].find_line(79).map(|l| l.line), Some(5));
+        assert_eq!(maps[0].find_line(99).map(|l| l.line), Some(4));
+        assert_eq!(maps[0].find_line(14), None);
+    }
+
+    #[test]
+    fn test_trace() {
+        let maps = test_maps();
+        let steps = trace(&maps, 79);
+
+        assert_eq!(
+            steps.iter().map(|s| s.value).collect::<Vec<_>>(),
+            vec![79, 81, 81, 81, 74, 78, 78, 82]
+        );
+        assert_eq!(
+            steps
+                .iter()
+                .map(|s| s.line.map(|l| l.line))
+                .collect::<Vec<_>>(),
+            vec![
+                None,
+                Some(5),
+                None,
+                None,
+                Some(20),
+                Some(25),
+                None,
+                Some(32)
+            ]
+        );
+        assert_eq!(steps[7].stage, "location");
+    }
+
+    #[test]
+    fn test_to_table() {
+        let maps = test_maps();
+
+        assert_eq!(
+            to_table(14, &trace(&maps, 14)),
+            concat!(
+                "Seed 14\n",
+                "  seed                   14\n",
+                "  soil                   14  identity\n",
+                "  fertilizer             53  line 10 (39 0 15)\n",
+                "  water                  49  line 13 (49 53 8)\n",
+                "  light                  42  line 20 (18 25 70)\n",
+                "  temperature            42  identity\n",
+                "  humidity               43  line 29 (1 0 69)\n",
+                "  location               43  identity\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let maps = test_maps();
+        let csv = to_csv(13, &trace(&maps, 13));
+
+        assert_eq!(csv.lines().count(), 7);
+        assert_eq!(csv.lines().next(), Some("13,soil,13,identity"));
+        assert_eq!(csv.lines().last(), Some("13,location,35,identity"));
+    }
+
+    #[test]
+    fn test_read_maps_overlap() {
+        let input = include_str!("test1.txt").replace("50 98 2", "50 97 2");
+
+        assert_eq!(
+            read_maps(&input).err(),
+            Some(OverlapError {
+                first: Range { start: 50, end: 98 },
+                second: Range { start: 97, end: 99 },
+            })
+        );
+    }
+
+    #[test]
+    fn test_trace_overflow() {
+        let maps = read_maps("seed-to-soil map:\n0 18446744073709551610 10").unwrap();
+        let steps = trace(&maps, 18446744073709551614);
+
+        assert_eq!(steps[1].value, 4);
+        assert_eq!(steps[1].line.map(|l| l.line), Some(2));
+        assert_eq!(steps[7].value, 4);
+    }
+}
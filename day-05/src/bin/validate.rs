@@ -0,0 +1,327 @@
+use day_05::{read_numbered_map, MapLine, MAPS};
+
+fn main() {
+    let input = match std::env::args().nth(1) {
+        Some(path) => std::fs::read_to_string(&path).unwrap_or_else(|e| {
+            eprintln!("Error: {}: {}", path, e);
+            std::process::exit(1);
+        }),
+        None => include_str!("input.txt").to_string(),
+    };
+
+    let (report, errors) = report(&input);
+    print!("{}", report);
+
+    if errors > 0 {
+        eprintln!("{} error(s) found", errors);
+        std::process::exit(1);
+    }
+}
+
+/// Validates every map in the almanac, returning the printed report and the
+/// number of errors found.
+fn report(input: &str) -> (String, usize) {
+    let mut out = String::new();
+    let mut errors = 0;
+
+    for name in MAPS {
+        let lines = read_numbered_map(input, name);
+        let issues = validate(&lines);
+        errors += issues.iter().filter(|i| i.is_error()).count();
+
+        out.push_str(&format!("{} map: {}\n", name, coverage(&lines)));
+        for issue in issues {
+            out.push_str(&format!("  {}\n", issue));
+        }
+    }
+
+    (out, errors)
+}
+
+#[derive(Debug, PartialEq)]
+enum Issue {
+    Overflow {
+        line: usize,
+    },
+    SourceOverlap {
+        lines: (usize, usize),
+        range: (usize, usize),
+    },
+    DestinationOverlap {
+        lines: (usize, usize),
+        range: (usize, usize),
+    },
+    IdentityOverlap {
+        line: usize,
+        range: (usize, usize),
+    },
+    Gap {
+        range: (usize, usize),
+    },
+    FallThrough {
+        range: (usize, usize),
+    },
+}
+
+impl Issue {
+    /// Gaps and fall-through regions are expected in a valid almanac; the
+    /// rest make the map ambiguous or impossible to evaluate.
+    fn is_error(&self) -> bool {
+        !matches!(self, Issue::Gap { .. } | Issue::FallThrough { .. })
+    }
+}
+
+impl std::fmt::Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::Overflow { line } => {
+                write!(f, "overflow: line {} runs past the largest value", line)
+            }
+            Issue::SourceOverlap { lines, range } => write!(
+                f,
+                "source overlap: lines {} and {} both cover {}..{}, line {} wins",
+                lines.0, lines.1, range.0, range.1, lines.0
+            ),
+            Issue::DestinationOverlap { lines, range } => write!(
+                f,
+                "non-injective: lines {} and {} both map onto {}..{}",
+                lines.0, lines.1, range.0, range.1
+            ),
+            Issue::IdentityOverlap { line, range } => write!(
+                f,
+                "non-injective: line {} maps onto {}..{}, which also maps to itself",
+                line, range.0, range.1
+            ),
+            Issue::Gap { range } => {
+                write!(f, "gap: {}..{} falls through as identity", range.0, range.1)
+            }
+            Issue::FallThrough { range } => {
+                write!(f, "fall-through: {}..{} is identity", range.0, range.1)
+            }
+        }
+    }
+}
+
+fn intersect(a: (usize, usize), b: (usize, usize)) -> Option<(usize, usize)> {
+    let range = (a.0.max(b.0), a.1.min(b.1));
+    (range.0 < range.1).then_some(range)
+}
+
+/// The source ranges that no map line covers, in ascending order. Lines that
+/// run past the largest value are cut off there.
+fn uncovered(lines: &[MapLine]) -> Vec<(usize, usize)> {
+    let mut sources = lines
+        .iter()
+        .map(|l| (l.from, l.from.saturating_add(l.size)))
+        .collect::<Vec<_>>();
+    sources.sort_unstable();
+
+    let mut ranges = vec![];
+    let mut next = 0;
+    for (start, end) in sources {
+        if start > next {
+            ranges.push((next, start));
+        }
+        next = next.max(end);
+    }
+    if next < usize::MAX {
+        ranges.push((next, usize::MAX));
+    }
+
+    ranges
+}
+
+fn validate(lines: &[MapLine]) -> Vec<Issue> {
+    let mut issues = vec![];
+
+    let (valid, overflowing): (Vec<MapLine>, Vec<MapLine>) = lines
+        .iter()
+        .partition(|l| l.from.checked_add(l.size).is_some() && l.to.checked_add(l.size).is_some());
+    for l in overflowing {
+        issues.push(Issue::Overflow { line: l.line });
+    }
+
+    for (i, a) in valid.iter().enumerate() {
+        for b in valid[i + 1..].iter() {
+            if let Some(range) = intersect((a.from, a.from + a.size), (b.from, b.from + b.size)) {
+                issues.push(Issue::SourceOverlap {
+                    lines: (a.line, b.line),
+                    range,
+                });
+            }
+            if let Some(range) = intersect((a.to, a.to + a.size), (b.to, b.to + b.size)) {
+                issues.push(Issue::DestinationOverlap {
+                    lines: (a.line, b.line),
+                    range,
+                });
+            }
+        }
+    }
+
+    let identity = uncovered(&valid);
+
+    for l in valid.iter() {
+        for region in identity.iter() {
+            if let Some(range) = intersect((l.to, l.to + l.size), *region) {
+                issues.push(Issue::IdentityOverlap {
+                    line: l.line,
+                    range,
+                });
+            }
+        }
+    }
+
+    for (i, range) in identity.iter().enumerate() {
+        if range.0 == 0 || i == identity.len() - 1 {
+            issues.push(Issue::FallThrough { range: *range });
+        } else {
+            issues.push(Issue::Gap { range: *range });
+        }
+    }
+
+    issues
+}
+
+/// How many source values the map lines cover between the lowest and
+/// highest source they mention.
+fn coverage(lines: &[MapLine]) -> String {
+    let start = lines.iter().map(|l| l.from).min().unwrap_or(0);
+    let end = lines
+        .iter()
+        .map(|l| l.from.saturating_add(l.size))
+        .max()
+        .unwrap_or(0);
+
+    let span = end - start;
+    let uncovered = uncovered(lines)
+        .iter()
+        .flat_map(|range| intersect(*range, (start, end)))
+        .map(|(a, b)| b - a)
+        .sum::<usize>();
+
+    format!(
+        "{} lines cover {} of {} values in {}..{}",
+        lines.len(),
+        span - uncovered,
+        span,
+        start,
+        end
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map_lines(lines: &[(usize, usize, usize)]) -> Vec<MapLine> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, (to, from, size))| MapLine {
+                line: i + 1,
+                to: *to,
+                from: *from,
+                size: *size,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_uncovered() {
+        let lines = map_lines(&[(50, 98, 2), (52, 50, 48)]);
+        assert_eq!(uncovered(&lines), vec![(0, 50), (100, usize::MAX)]);
+
+        let lines = map_lines(&[(0, 10, 5), (0, 20, 5), (0, 12, 5)]);
+        assert_eq!(uncovered(&lines), vec![(0, 10), (17, 20), (25, usize::MAX)]);
+    }
+
+    #[test]
+    fn test_validate_clean_map() {
+        let lines = map_lines(&[(50, 98, 2), (52, 50, 48)]);
+
+        assert_eq!(
+            validate(&lines),
+            vec![
+                Issue::FallThrough { range: (0, 50) },
+                Issue::FallThrough {
+                    range: (100, usize::MAX)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_overlaps() {
+        let lines = map_lines(&[(100, 10, 10), (105, 15, 10), (0, 30, 5)]);
+
+        // Lines 1 and 2 also land on values above 35, which map to themselves
+
+        assert_eq!(
+            validate(&lines),
+            vec![
+                Issue::SourceOverlap {
+                    lines: (1, 2),
+                    range: (15, 20)
+                },
+                Issue::DestinationOverlap {
+                    lines: (1, 2),
+                    range: (105, 110)
+                },
+                Issue::IdentityOverlap {
+                    line: 1,
+                    range: (100, 110)
+                },
+                Issue::IdentityOverlap {
+                    line: 2,
+                    range: (105, 115)
+                },
+                Issue::IdentityOverlap {
+                    line: 3,
+                    range: (0, 5)
+                },
+                Issue::FallThrough { range: (0, 10) },
+                Issue::Gap { range: (25, 30) },
+                Issue::FallThrough {
+                    range: (35, usize::MAX)
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_overflow() {
+        let lines = map_lines(&[(0, usize::MAX - 1, 5), (usize::MAX, 0, 2)]);
+
+        let issues = validate(&lines);
+        assert_eq!(issues[0], Issue::Overflow { line: 1 });
+        assert_eq!(issues[1], Issue::Overflow { line: 2 });
+        assert!(issues[2..].iter().all(|i| !i.is_error()));
+    }
+
+    #[test]
+    fn test_report_overflow() {
+        let (report, errors) = report("seed-to-soil map:\n0 18446744073709551610 10");
+
+        assert_eq!(errors, 1);
+        assert!(report.starts_with(concat!(
+            "seed-to-soil map: 1 lines cover 5 of 5 values in 18446744073709551610..18446744073709551615\n",
+            "  overflow: line 2 runs past the largest value\n",
+        )));
+    }
+
+    #[test]
+    fn test_validate_input() {
+        let input = include_str!("test1.txt");
+
+        for name in MAPS {
+            let issues = validate(&read_numbered_map(input, name));
+            assert!(issues.iter().all(|i| !i.is_error()), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_coverage() {
+        let lines = map_lines(&[(0, 10, 5), (0, 20, 5)]);
+        assert_eq!(coverage(&lines), "2 lines cover 10 of 15 values in 10..25");
+    }
+}
@@ -0,0 +1,550 @@
+/// The almanac's maps, in the order a seed passes through them.
+pub const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+pub fn read_seeds(input: &str) -> Vec<usize> {
+    input
+        .split('\n')
+        .next()
+        .unwrap()
+        .strip_prefix("seeds: ")
+        .unwrap()
+        .split(" ")
+        .map(|s| s.parse().unwrap())
+        .collect()
+}
+
+pub fn read_seed_ranges(input: &str) -> Vec<(usize, usize)> {
+    input
+        .split('\n')
+        .next()
+        .unwrap()
+        .strip_prefix("seeds: ")
+        .unwrap()
+        .split(" ")
+        .map(|s| s.parse().unwrap())
+        .collect::<Vec<usize>>()
+        .chunks(2)
+        .map(|chunk| (chunk[0], chunk[0].saturating_add(chunk[1])))
+        .collect()
+}
+
+pub fn read_map(input: &str, map_name: &str) -> Vec<(usize, usize, usize)> {
+    input
+        .split("\n")
+        .skip_while(|s| !s.starts_with(map_name))
+        .skip(1)
+        .take_while(|l| l.starts_with(|c: char| c.is_numeric()))
+        .map(|l| {
+            let numbers: Vec<usize> = l.split_whitespace().map(|s| s.parse().unwrap()).collect();
+            (numbers[0], numbers[1], numbers[2])
+        })
+        .collect()
+}
+
+/// A map line along with the line number it was read from.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct MapLine {
+    pub line: usize,
+    pub to: usize,
+    pub from: usize,
+    pub size: usize,
+}
+
+/// Reads a map like `read_map`, but keeps the 1-based line number of every
+/// entry so reports can point back at the input.
+pub fn read_numbered_map(input: &str, map_name: &str) -> Vec<MapLine> {
+    input
+        .split('\n')
+        .enumerate()
+        .skip_while(|(_, s)| !s.starts_with(map_name))
+        .skip(1)
+        .take_while(|(_, l)| l.starts_with(|c: char| c.is_numeric()))
+        .map(|(i, l)| {
+            let numbers: Vec<usize> = l.split_whitespace().map(|s| s.parse().unwrap()).collect();
+            MapLine {
+                line: i + 1,
+                to: numbers[0],
+                from: numbers[1],
+                size: numbers[2],
+            }
+        })
+        .collect()
+}
+
+/// The source values `start..end` of a map line.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Range {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Range {
+    pub fn contains(&self, value: usize) -> bool {
+        value >= self.start && value < self.end
+    }
+}
+
+/// How far a map line moves a value from its source to its destination.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Offset(i64);
+
+impl Offset {
+    pub fn apply(&self, value: usize) -> usize {
+        shift(value, self.0)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RangeEntry {
+    source: Range,
+    offset: Offset,
+    /// The position of the map line this came from, in the order the lines
+    /// were given to `RangeMap::new`.
+    index: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct OverlapError {
+    pub first: Range,
+    pub second: Range,
+}
+
+impl std::fmt::Display for OverlapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "map lines overlap: {}..{} and {}..{}",
+            self.first.start, self.first.end, self.second.start, self.second.end
+        )
+    }
+}
+
+/// A single almanac map with its lines sorted by source, so lookups are a
+/// binary search rather than a scan.
+#[derive(Debug, PartialEq)]
+pub struct RangeMap {
+    entries: Vec<RangeEntry>,
+}
+
+impl RangeMap {
+    pub fn new(map: &[(usize, usize, usize)]) -> Result<Self, OverlapError> {
+        let mut entries = map
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, _, size))| *size > 0)
+            .map(|(index, (to, from, size))| RangeEntry {
+                source: Range {
+                    start: *from,
+                    end: from.saturating_add(*size),
+                },
+                offset: Offset(*to as i64 - *from as i64),
+                index,
+            })
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|e| e.source.start);
+
+        for pair in entries.windows(2) {
+            if pair[1].source.start < pair[0].source.end {
+                return Err(OverlapError {
+                    first: pair[0].source,
+                    second: pair[1].source,
+                });
+            }
+        }
+
+        Ok(Self { entries })
+    }
+
+    fn entry_for(&self, value: usize) -> Option<&RangeEntry> {
+        let i = self.entries.partition_point(|e| e.source.start <= value);
+
+        i.checked_sub(1)
+            .map(|i| &self.entries[i])
+            .filter(|entry| entry.source.contains(value))
+    }
+
+    pub fn lookup(&self, value: usize) -> usize {
+        self.entry_for(value)
+            .map_or(value, |entry| entry.offset.apply(value))
+    }
+
+    /// The position of the line covering `value`, in the order the lines were
+    /// given to `new`, or `None` if it falls through as identity.
+    pub fn line_for(&self, value: usize) -> Option<usize> {
+        self.entry_for(value).map(|entry| entry.index)
+    }
+}
+
+/// Values in `start..end` are moved by `offset`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Segment {
+    pub start: usize,
+    pub end: usize,
+    pub offset: i64,
+}
+
+pub fn shift(value: usize, offset: i64) -> usize {
+    value.wrapping_add_signed(offset as isize)
+}
+
+/// A map as a sorted list of segments covering every value from 0 upwards,
+/// with identity segments filling the gaps between map lines. Two of these
+/// can be composed into a single map, so a whole chain of lookups becomes one
+/// binary search.
+#[derive(Debug, PartialEq)]
+pub struct PiecewiseMap {
+    segments: Vec<Segment>,
+}
+
+impl PiecewiseMap {
+    pub fn identity() -> Self {
+        Self {
+            segments: vec![Segment {
+                start: 0,
+                end: usize::MAX,
+                offset: 0,
+            }],
+        }
+    }
+
+    /// The segments in order, from 0 upwards.
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Appends a segment, merging it into the previous one if they line up.
+    fn push(&mut self, segment: Segment) {
+        match self.segments.last_mut() {
+            Some(last) if last.end == segment.start && last.offset == segment.offset => {
+                last.end = segment.end
+            }
+            _ => self.segments.push(segment),
+        }
+    }
+
+    pub fn segment_for(&self, value: usize) -> &Segment {
+        let i = self.segments.partition_point(|s| s.start <= value);
+        &self.segments[i - 1]
+    }
+
+    pub fn lookup(&self, value: usize) -> usize {
+        shift(value, self.segment_for(value).offset)
+    }
+
+    /// The map that applies `self` and then `next`.
+    pub fn then(&self, next: &PiecewiseMap) -> PiecewiseMap {
+        let mut result = Self { segments: vec![] };
+
+        for segment in self.segments.iter() {
+            let mut start = segment.start;
+
+            while start < segment.end {
+                let image = shift(start, segment.offset);
+                let target = next.segment_for(image);
+                let end = start + (target.end - image).min(segment.end - start);
+
+                result.push(Segment {
+                    start,
+                    end,
+                    offset: segment.offset + target.offset,
+                });
+                start = end;
+            }
+        }
+
+        result
+    }
+}
+
+/// Every value between two consecutive line boundaries moves by the same
+/// offset, so one lookup per boundary is enough to fill in the segments.
+impl From<&RangeMap> for PiecewiseMap {
+    fn from(map: &RangeMap) -> Self {
+        let mut result = Self { segments: vec![] };
+
+        let mut bounds = map
+            .entries
+            .iter()
+            .flat_map(|e| [e.source.start, e.source.end])
+            .chain([0, usize::MAX])
+            .collect::<Vec<_>>();
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        for pair in bounds.windows(2) {
+            let (start, end) = (pair[0], pair[1]);
+            result.push(Segment {
+                start,
+                end,
+                offset: map.lookup(start) as i64 - start as i64,
+            });
+        }
+
+        result
+    }
+}
+
+/// Writes the map in the almanac's own `destination source length` format,
+/// so it can be read back with `read_map`.
+impl std::fmt::Display for PiecewiseMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for segment in self.segments.iter() {
+            writeln!(
+                f,
+                "{} {} {}",
+                shift(segment.start, segment.offset),
+                segment.start,
+                segment.end - segment.start
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Composes the maps in order, failing on the first one with overlapping lines.
+#[macro_export]
+macro_rules! pipeline {
+    ( $($maps:tt)=>+ ) => {
+        {
+            let ret: Result<$crate::PiecewiseMap, $crate::OverlapError> =
+                Ok($crate::PiecewiseMap::identity());
+            $(
+                let ret = ret.and_then(|ret| {
+                    Ok(ret.then(&$crate::PiecewiseMap::from(&$crate::RangeMap::new(&$maps)?)))
+                });
+            )*
+            ret
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_seeds() {
+        let input = "seeds: 1 2 3\n\nseed-to-soil map:\n 1 2 3";
+        assert_eq!(read_seeds(input), vec![1, 2, 3]);
+
+        let input = include_str!("bin/test1.txt");
+        assert_eq!(read_seeds(input), vec![79, 14, 55, 13]);
+    }
+
+    #[test]
+    fn test_read_seed_ranges() {
+        let input = "seeds: 1 2 3 4\n\nseed-to-soil map:\n 1 2 3 4";
+        assert_eq!(read_seed_ranges(input), vec![(1, 3), (3, 7)]);
+
+        let input = include_str!("bin/test1.txt");
+        assert_eq!(read_seed_ranges(input), vec![(79, 93), (55, 68)]);
+
+        let input = "seeds: 18446744073709551610 10";
+        assert_eq!(
+            read_seed_ranges(input),
+            vec![(18446744073709551610, usize::MAX)]
+        );
+    }
+
+    #[test]
+    fn test_read_map() {
+        let input = include_str!("bin/test1.txt");
+        assert_eq!(
+            read_map(input, "seed-to-soil"),
+            vec![(50, 98, 2), (52, 50, 48)]
+        );
+
+        assert_eq!(
+            read_map(input, "temperature-to-humidity"),
+            vec![(0, 69, 1), (1, 0, 69),]
+        );
+    }
+
+    #[test]
+    fn test_read_numbered_map() {
+        let input = include_str!("bin/test1.txt");
+
+        assert_eq!(
+            read_numbered_map(input, "seed-to-soil"),
+            vec![
+                MapLine {
+                    line: 4,
+                    to: 50,
+                    from: 98,
+                    size: 2
+                },
+                MapLine {
+                    line: 5,
+                    to: 52,
+                    from: 50,
+                    size: 48
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_range_map() {
+        let map = RangeMap::new(&[(50, 98, 2), (52, 50, 48), (0, 0, 0)]).unwrap();
+
+        assert_eq!(
+            map.entries,
+            vec![
+                RangeEntry {
+                    source: Range { start: 50, end: 98 },
+                    offset: Offset(2),
+                    index: 1
+                },
+                RangeEntry {
+                    source: Range {
+                        start: 98,
+                        end: 100
+                    },
+                    offset: Offset(-48),
+                    index: 0
+                },
+            ]
+        );
+
+        assert_eq!(map.lookup(79), 81);
+        assert_eq!(map.lookup(14), 14);
+        assert_eq!(map.lookup(55), 57);
+        assert_eq!(map.lookup(13), 13);
+        assert_eq!(map.lookup(99), 51);
+        assert_eq!(map.lookup(100), 100);
+
+        assert_eq!(map.line_for(79), Some(1));
+        assert_eq!(map.line_for(99), Some(0));
+        assert_eq!(map.line_for(14), None);
+    }
+
+    #[test]
+    fn test_range_map_overlap() {
+        assert_eq!(
+            RangeMap::new(&[(200, 15, 10), (100, 10, 10)]),
+            Err(OverlapError {
+                first: Range { start: 10, end: 20 },
+                second: Range { start: 15, end: 25 },
+            })
+        );
+    }
+
+    #[test]
+    fn test_range_map_matches_lines() {
+        let input = include_str!("bin/test1.txt");
+        let mut maps = MAPS.map(|name| read_map(input, name)).to_vec();
+
+        // Adjacent lines, a gap, an empty line and a line far above the rest
+        maps.push(vec![
+            (1_000_000, 40, 60),
+            (0, 5, 3),
+            (100, 8, 2),
+            (50, 30, 0),
+            (7, 20, 1),
+            (0, 1 << 40, 1 << 20),
+        ]);
+
+        for lines in maps {
+            let map = RangeMap::new(&lines).unwrap();
+            let expected = |value: usize| {
+                lines
+                    .iter()
+                    .find(|(_, from, size)| value.checked_sub(*from).is_some_and(|d| d < *size))
+                    .map_or(value, |(to, from, _)| to + (value - from))
+            };
+
+            let values = lines
+                .iter()
+                .flat_map(|(to, from, size)| [*to, *from, from + size])
+                .flat_map(|v| [v.saturating_sub(1), v, v + 1])
+                .chain(0..120);
+
+            for value in values {
+                assert_eq!(map.lookup(value), expected(value));
+            }
+        }
+    }
+
+    #[test]
+    fn test_piecewise_map() {
+        let map = PiecewiseMap::from(&RangeMap::new(&[(50, 98, 2), (52, 50, 48)]).unwrap());
+
+        assert_eq!(
+            map.segments,
+            vec![
+                Segment {
+                    start: 0,
+                    end: 50,
+                    offset: 0
+                },
+                Segment {
+                    start: 50,
+                    end: 98,
+                    offset: 2
+                },
+                Segment {
+                    start: 98,
+                    end: 100,
+                    offset: -48
+                },
+                Segment {
+                    start: 100,
+                    end: usize::MAX,
+                    offset: 0
+                },
+            ]
+        );
+
+        assert_eq!(map.lookup(79), 81);
+        assert_eq!(map.lookup(14), 14);
+        assert_eq!(map.lookup(99), 51);
+        assert_eq!(map.lookup(100), 100);
+    }
+
+    #[test]
+    fn test_piecewise_map_overlap() {
+        // The first matching line used to win; overlapping lines are now rejected
+        assert_eq!(
+            RangeMap::new(&[(100, 10, 10), (200, 15, 10)]),
+            Err(OverlapError {
+                first: Range { start: 10, end: 20 },
+                second: Range { start: 15, end: 25 },
+            })
+        );
+
+        let map = PiecewiseMap::from(&RangeMap::new(&[(100, 10, 5), (200, 15, 10)]).unwrap());
+        assert_eq!(map.lookup(12), 102);
+        assert_eq!(map.lookup(17), 202);
+        assert_eq!(map.lookup(22), 207);
+    }
+
+    #[test]
+    fn test_pipeline() {
+        let input = include_str!("bin/test1.txt");
+        let maps = MAPS.map(|name| read_map(input, name));
+        let [a, b, c, d, e, f, g] = &maps;
+
+        let composed = pipeline!(a => b => c => d => e => f => g).unwrap();
+
+        let range_maps = maps.each_ref().map(|lines| RangeMap::new(lines).unwrap());
+        for seed in 0..200 {
+            let expected = range_maps.iter().fold(seed, |value, map| map.lookup(value));
+            assert_eq!(composed.lookup(seed), expected);
+        }
+
+        let serialized = format!("seed-to-location map:\n{}", composed);
+        assert_eq!(
+            PiecewiseMap::from(&RangeMap::new(&read_map(&serialized, "seed-to-location")).unwrap()),
+            composed
+        );
+    }
+}
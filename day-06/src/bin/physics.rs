@@ -0,0 +1,457 @@
+use day_06::{calculate_distance, parse_races, ParseMode, Race};
+
+fn main() {
+    let input = include_str!("input.txt");
+
+    let mut mode = ParseMode::Separate;
+    let mut models: Vec<Box<dyn BoatModel>> = vec![];
+    for arg in std::env::args().skip(1) {
+        if let Some(m) = arg.strip_prefix("--mode=") {
+            mode = m.parse().unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            });
+        } else {
+            models.push(parse_model(&arg).unwrap_or_else(|| {
+                eprintln!("Invalid boat model: {}", arg);
+                std::process::exit(1);
+            }));
+        }
+    }
+    if models.is_empty() {
+        models = default_models();
+    }
+
+    let races = parse_races(input, &mode).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+
+    for model in models.iter() {
+        println!("{}", model.name());
+        for (i, race) in races.iter().enumerate() {
+            let best = model.optimal_hold(race.time);
+            let best_distance = model.distance(best, race.time);
+
+            match model.winning_interval(race) {
+                Some((first, last)) => println!(
+                    "  race {}: hold {}..={} ({} ways), best hold {} ({:.1})",
+                    i + 1,
+                    first,
+                    last,
+                    last - first + 1,
+                    best,
+                    best_distance
+                ),
+                None => println!(
+                    "  race {}: no winning hold, best hold {} ({:.1})",
+                    i + 1,
+                    best,
+                    best_distance
+                ),
+            }
+        }
+    }
+}
+
+/// How far a boat travels in a race of `time` ms when the button is held for
+/// the first `hold` ms of it.
+trait BoatModel {
+    fn name(&self) -> String;
+
+    fn distance(&self, hold: usize, time: usize) -> f64;
+
+    /// The hold time that travels furthest. The default searches for it
+    /// numerically.
+    fn optimal_hold(&self, time: usize) -> usize {
+        optimal_hold(|hold| self.distance(hold, time), time)
+    }
+
+    /// The first and last hold times that beat the record, or `None` if none
+    /// do. The default searches for them numerically.
+    fn winning_interval(&self, race: &Race) -> Option<(usize, usize)> {
+        let best = self.optimal_hold(race.time);
+        winning_interval(|hold| self.distance(hold, race.time), race, best)
+    }
+}
+
+/// Finds the peak of a distance curve over the holds `0..=time` with a ternary
+/// search. The curve has to rise to its peak and fall after it, which holds for
+/// every model here: holding longer is faster but leaves less time to move.
+fn optimal_hold(distance: impl Fn(usize) -> f64, time: usize) -> usize {
+    let (mut low, mut high) = (0, time);
+
+    while high - low > 2 {
+        let a = low + (high - low) / 3;
+        let b = high - (high - low) / 3;
+
+        if distance(a) < distance(b) {
+            low = a + 1;
+        } else {
+            high = b;
+        }
+    }
+
+    (low..=high)
+        .max_by(|a, b| distance(*a).total_cmp(&distance(*b)))
+        .unwrap()
+}
+
+/// The first value in `low..high` for which `pred` is false, assuming it is
+/// true for everything before that and false for everything after.
+fn partition_point(mut low: usize, mut high: usize, pred: impl Fn(usize) -> bool) -> usize {
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if pred(mid) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+
+    low
+}
+
+/// Binary searches either side of the peak for the outermost holds that beat
+/// the record.
+fn winning_interval(
+    distance: impl Fn(usize) -> f64,
+    race: &Race,
+    best: usize,
+) -> Option<(usize, usize)> {
+    let record = race.distance as f64;
+    if distance(best) <= record {
+        return None;
+    }
+
+    let first = partition_point(0, best, |hold| distance(hold) <= record);
+    let last = partition_point(best, race.time + 1, |hold| distance(hold) > record) - 1;
+
+    Some((first, last))
+}
+
+/// The puzzle's rule: the boat moves at one millimetre per millisecond for
+/// every millisecond the button was held.
+struct Coast;
+
+impl BoatModel for Coast {
+    fn name(&self) -> String {
+        "coast".to_string()
+    }
+
+    fn distance(&self, hold: usize, time: usize) -> f64 {
+        calculate_distance(hold, time) as f64
+    }
+
+    fn optimal_hold(&self, time: usize) -> usize {
+        time / 2
+    }
+
+    /// Solves `hold * (time - hold) > record` exactly, using the float roots
+    /// only as a starting point.
+    fn winning_interval(&self, race: &Race) -> Option<(usize, usize)> {
+        let wins = |hold: usize| calculate_distance(hold, race.time) > race.distance;
+
+        let best = self.optimal_hold(race.time);
+        if !wins(best) {
+            return None;
+        }
+
+        let (time, record) = (race.time as f64, race.distance as f64);
+        let root = (time - (time * time - 4.0 * record).sqrt()) / 2.0;
+
+        let mut first = (root as usize).min(best);
+        while first > 0 && wins(first - 1) {
+            first -= 1;
+        }
+        while !wins(first) {
+            first += 1;
+        }
+
+        Some((first, race.time - first))
+    }
+}
+
+/// Like `Coast`, but every millisecond held adds `rate` mm/ms of speed.
+struct Acceleration {
+    rate: f64,
+}
+
+impl BoatModel for Acceleration {
+    fn name(&self) -> String {
+        format!("accelerate:{}", self.rate)
+    }
+
+    fn distance(&self, hold: usize, time: usize) -> f64 {
+        self.rate * calculate_distance(hold, time) as f64
+    }
+
+    fn optimal_hold(&self, time: usize) -> usize {
+        time / 2
+    }
+
+    /// Solves `rate * hold * (time - hold) > record`, using the float roots
+    /// only as a starting point like `Coast` does.
+    fn winning_interval(&self, race: &Race) -> Option<(usize, usize)> {
+        let record = race.distance as f64;
+        let wins = |hold: usize| self.distance(hold, race.time) > record;
+
+        let best = self.optimal_hold(race.time);
+        if !wins(best) {
+            return None;
+        }
+
+        // The roots sit at (time ± sqrt(time² - 4 * record / rate)) / 2
+        let time = race.time as f64;
+        let discriminant = (time * time - 4.0 * record / self.rate).max(0.0);
+        let root = (time - discriminant.sqrt()) / 2.0;
+
+        let mut first = (root as usize).min(best);
+        while first > 0 && wins(first - 1) {
+            first -= 1;
+        }
+        while !wins(first) {
+            first += 1;
+        }
+
+        race.time.checked_sub(first).map(|last| (first, last))
+    }
+}
+
+/// Accelerates at `rate` per millisecond held, but the boat cannot go faster
+/// than `cap`.
+struct TopSpeed {
+    rate: f64,
+    cap: f64,
+}
+
+impl BoatModel for TopSpeed {
+    fn name(&self) -> String {
+        format!("cap:{}:{}", self.rate, self.cap)
+    }
+
+    fn distance(&self, hold: usize, time: usize) -> f64 {
+        (self.rate * hold as f64).min(self.cap) * (time - hold) as f64
+    }
+}
+
+/// Accelerates at `rate` per millisecond held, then loses `drag` of its speed
+/// every millisecond it moves, so it covers `v * (1 - e^(-drag * t)) / drag`.
+/// The winning holds have no closed form, so they come from the solver.
+struct Drag {
+    rate: f64,
+    drag: f64,
+}
+
+impl BoatModel for Drag {
+    fn name(&self) -> String {
+        format!("drag:{}:{}", self.rate, self.drag)
+    }
+
+    fn distance(&self, hold: usize, time: usize) -> f64 {
+        let speed = self.rate * hold as f64;
+        let moving = (time - hold) as f64;
+
+        if self.drag == 0.0 {
+            speed * moving
+        } else {
+            speed * (1.0 - (-self.drag * moving).exp()) / self.drag
+        }
+    }
+}
+
+fn default_models() -> Vec<Box<dyn BoatModel>> {
+    vec![
+        Box::new(Coast),
+        Box::new(Acceleration { rate: 2.0 }),
+        Box::new(TopSpeed {
+            rate: 1.0,
+            cap: 20.0,
+        }),
+        Box::new(Drag {
+            rate: 1.0,
+            drag: 0.01,
+        }),
+    ]
+}
+
+/// Reads a model name as printed by `BoatModel::name`, e.g. `coast`,
+/// `accelerate:2`, `cap:1:20` or `drag:1:0.01`. Every rate, cap and drag has to
+/// be a positive number, since anything else leaves the boat unable to win.
+fn parse_model(s: &str) -> Option<Box<dyn BoatModel>> {
+    let mut parts = s.split(':');
+    let name = parts.next()?;
+    let params = parts
+        .map(|p| p.parse::<f64>().ok().filter(|x| x.is_finite() && *x > 0.0))
+        .collect::<Option<Vec<_>>>()?;
+
+    match (name, params.as_slice()) {
+        ("coast", []) => Some(Box::new(Coast)),
+        ("accelerate", [rate]) => Some(Box::new(Acceleration { rate: *rate })),
+        ("cap", [rate, cap]) => Some(Box::new(TopSpeed {
+            rate: *rate,
+            cap: *cap,
+        })),
+        ("drag", [rate, drag]) => Some(Box::new(Drag {
+            rate: *rate,
+            drag: *drag,
+        })),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_races() -> Vec<Race> {
+        parse_races(include_str!("test.txt"), &ParseMode::Separate).unwrap()
+    }
+
+    /// Checks every hold, for comparing a model against the solver.
+    fn brute_force(model: &dyn BoatModel, race: &Race) -> Option<(usize, usize)> {
+        let wins = (0..=race.time)
+            .filter(|hold| model.distance(*hold, race.time) > race.distance as f64)
+            .collect::<Vec<_>>();
+
+        Some((*wins.first()?, *wins.last()?))
+    }
+
+    #[test]
+    fn test_coast() {
+        let intervals = test_races()
+            .iter()
+            .map(|race| Coast.winning_interval(race))
+            .collect::<Vec<_>>();
+
+        assert_eq!(intervals, vec![Some((2, 5)), Some((4, 11)), Some((11, 19))]);
+        assert_eq!(Coast.optimal_hold(7), 3);
+        assert_eq!(
+            Coast.winning_interval(&Race {
+                time: 71530,
+                distance: 940200
+            }),
+            Some((14, 71516))
+        );
+        assert_eq!(
+            Coast.winning_interval(&Race {
+                time: 7,
+                distance: 12
+            }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_closed_forms_match_solver() {
+        let mut models: Vec<Box<dyn BoatModel>> = vec![Box::new(Coast)];
+        for rate in [0.5, 3.0, 1.1, 0.3, 2.7, 1.0 / 3.0] {
+            models.push(Box::new(Acceleration { rate }));
+        }
+
+        let mut races = test_races();
+        for time in 0..=40 {
+            for distance in (0..=time * time / 2).step_by(7) {
+                races.push(Race { time, distance });
+            }
+        }
+
+        for model in models.iter() {
+            for race in races.iter() {
+                let race = *race;
+                let best = model.optimal_hold(race.time);
+                let solved = winning_interval(|h| model.distance(h, race.time), &race, best);
+
+                assert_eq!(model.winning_interval(&race), solved);
+                assert_eq!(
+                    model.winning_interval(&race),
+                    brute_force(model.as_ref(), &race)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_acceleration() {
+        let model = Acceleration { rate: 1.1 };
+        let race = |time, distance| Race { time, distance };
+
+        assert_eq!(model.winning_interval(&race(13, 33)), Some((4, 9)));
+        assert_eq!(model.winning_interval(&race(16, 66)), Some((7, 9)));
+        assert_eq!(model.winning_interval(&race(27, 55)), Some((2, 25)));
+        assert_eq!(model.winning_interval(&race(0, 0)), None);
+    }
+
+    #[test]
+    fn test_top_speed() {
+        let model = TopSpeed {
+            rate: 1.0,
+            cap: 4.0,
+        };
+
+        assert_eq!(model.distance(2, 10), 16.0);
+        assert_eq!(model.distance(6, 10), 16.0);
+        assert_eq!(model.optimal_hold(10), 4);
+        assert_eq!(
+            model.winning_interval(&Race {
+                time: 10,
+                distance: 20
+            }),
+            Some((3, 4))
+        );
+    }
+
+    #[test]
+    fn test_drag() {
+        let model = Drag {
+            rate: 1.0,
+            drag: 0.1,
+        };
+
+        // Drag makes the boat favour longer holds than the midpoint
+        assert!(model.optimal_hold(30) > 15);
+
+        for race in test_races() {
+            assert_eq!(model.winning_interval(&race), brute_force(&model, &race));
+        }
+
+        let no_drag = Drag {
+            rate: 1.0,
+            drag: 0.0,
+        };
+        for race in test_races() {
+            assert_eq!(
+                no_drag.winning_interval(&race),
+                Coast.winning_interval(&race)
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_model() {
+        for name in ["coast", "accelerate:2", "cap:1:20", "drag:1:0.01"] {
+            assert_eq!(parse_model(name).map(|m| m.name()), Some(name.to_string()));
+        }
+        assert!(parse_model("accelerate").is_none());
+        assert!(parse_model("accelerate:0").is_none());
+        assert!(parse_model("drag:1:x").is_none());
+        assert!(parse_model("sail").is_none());
+    }
+
+    #[test]
+    fn test_parse_model_not_positive() {
+        for name in [
+            "accelerate:-1",
+            "accelerate:inf",
+            "cap:0:20",
+            "cap:1:0",
+            "cap:1:-5",
+            "drag:-1:0.01",
+            "drag:1:0",
+            "drag:1:-0.01",
+            "drag:1:NaN",
+        ] {
+            assert!(parse_model(name).is_none(), "{}", name);
+        }
+    }
+}
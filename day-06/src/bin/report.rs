@@ -0,0 +1,248 @@
+use day_06::{calculate_distance, parse_races, ParseMode, Race};
+
+fn main() {
+    let input = include_str!("input.txt");
+    let json = std::env::args().any(|arg| arg == "--json");
+
+    let mode =
+        match std::env::args().find_map(|arg| arg.strip_prefix("--mode=").map(str::to_string)) {
+            Some(mode) => mode.parse().unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }),
+            None => ParseMode::Separate,
+        };
+
+    let races = parse_races(input, &mode).unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+    let reports = races.iter().map(RaceReport::new).collect::<Vec<_>>();
+
+    if json {
+        print!("{}", to_json(&reports));
+    } else {
+        print!("{}", to_table(&reports));
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct RaceReport {
+    race: Race,
+    /// The first and last hold times that beat the record.
+    winning: Option<(usize, usize)>,
+    best_hold: usize,
+    max_distance: usize,
+    /// How far the best hold goes past the record. Negative if it falls short.
+    margin: i64,
+}
+
+impl RaceReport {
+    fn new(race: &Race) -> Self {
+        let best_hold = race.time / 2;
+        let max_distance = calculate_distance(best_hold, race.time);
+        let wins = |hold| calculate_distance(hold, race.time) > race.distance;
+
+        // Distance rises up to the best hold and falls symmetrically after it,
+        // so the first winning hold fixes the last one too.
+        let winning = wins(best_hold).then(|| {
+            let (mut low, mut high) = (0, best_hold);
+            while low < high {
+                let mid = low + (high - low) / 2;
+                if wins(mid) {
+                    high = mid;
+                } else {
+                    low = mid + 1;
+                }
+            }
+            (low, race.time - low)
+        });
+
+        Self {
+            race: *race,
+            winning,
+            best_hold,
+            max_distance,
+            margin: max_distance as i64 - race.distance as i64,
+        }
+    }
+
+    fn ways(&self) -> usize {
+        self.winning.map_or(0, |(first, last)| last - first + 1)
+    }
+}
+
+const HEADER: [&str; 9] = [
+    "Race", "Time", "Record", "First", "Last", "Ways", "Best", "Max", "Margin",
+];
+
+fn to_table(reports: &[RaceReport]) -> String {
+    let rows = reports
+        .iter()
+        .enumerate()
+        .map(|(i, r)| {
+            let (first, last) = match r.winning {
+                Some((first, last)) => (first.to_string(), last.to_string()),
+                None => ("-".to_string(), "-".to_string()),
+            };
+
+            [
+                (i + 1).to_string(),
+                r.race.time.to_string(),
+                r.race.distance.to_string(),
+                first,
+                last,
+                r.ways().to_string(),
+                r.best_hold.to_string(),
+                r.max_distance.to_string(),
+                r.margin.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let widths = (0..HEADER.len())
+        .map(|c| {
+            rows.iter()
+                .map(|row| row[c].len())
+                .chain([HEADER[c].len()])
+                .max()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let header = HEADER.map(|s| s.to_string());
+    let mut out = String::new();
+    for row in [&header].into_iter().chain(rows.iter()) {
+        let cells = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+            .collect::<Vec<_>>();
+        out.push_str(&cells.join("  "));
+        out.push('\n');
+    }
+
+    out
+}
+
+fn to_json(reports: &[RaceReport]) -> String {
+    let races = reports
+        .iter()
+        .enumerate()
+        .map(|(i, r)| {
+            let (first, last) = match r.winning {
+                Some((first, last)) => (first.to_string(), last.to_string()),
+                None => ("null".to_string(), "null".to_string()),
+            };
+
+            format!(
+                concat!(
+                    "  {{\"race\": {}, \"time\": {}, \"record\": {}, ",
+                    "\"first_win\": {}, \"last_win\": {}, \"ways\": {}, ",
+                    "\"best_hold\": {}, \"max_distance\": {}, \"margin\": {}}}"
+                ),
+                i + 1,
+                r.race.time,
+                r.race.distance,
+                first,
+                last,
+                r.ways(),
+                r.best_hold,
+                r.max_distance,
+                r.margin
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if races.is_empty() {
+        "[]\n".to_string()
+    } else {
+        format!("[\n{}\n]\n", races.join(",\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use day_06::winning_moves;
+
+    fn test_reports() -> Vec<RaceReport> {
+        parse_races(include_str!("test.txt"), &ParseMode::Separate)
+            .unwrap()
+            .iter()
+            .map(RaceReport::new)
+            .collect()
+    }
+
+    #[test]
+    fn test_race_report() {
+        let reports = test_reports();
+
+        assert_eq!(
+            reports[0],
+            RaceReport {
+                race: Race {
+                    time: 7,
+                    distance: 9
+                },
+                winning: Some((2, 5)),
+                best_hold: 3,
+                max_distance: 12,
+                margin: 3,
+            }
+        );
+        assert_eq!(
+            reports.iter().map(|r| r.ways()).collect::<Vec<_>>(),
+            vec![4, 8, 9]
+        );
+        assert_eq!(
+            reports.iter().map(|r| r.ways()).collect::<Vec<_>>(),
+            reports
+                .iter()
+                .map(|r| winning_moves(r.race.time, r.race.distance))
+                .collect::<Vec<_>>()
+        );
+
+        let lost = RaceReport::new(&Race {
+            time: 7,
+            distance: 12,
+        });
+        assert_eq!(lost.winning, None);
+        assert_eq!(lost.ways(), 0);
+        assert_eq!(lost.margin, 0);
+    }
+
+    #[test]
+    fn test_to_table() {
+        assert_eq!(
+            to_table(&test_reports()),
+            concat!(
+                "Race  Time  Record  First  Last  Ways  Best  Max  Margin\n",
+                "   1     7       9      2     5     4     3   12       3\n",
+                "   2    15      40      4    11     8     7   56      16\n",
+                "   3    30     200     11    19     9    15  225      25\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json = to_json(&test_reports()[..1]);
+
+        assert_eq!(
+            json,
+            concat!(
+                "[\n",
+                "  {\"race\": 1, \"time\": 7, \"record\": 9, \"first_win\": 2, \"last_win\": 5, ",
+                "\"ways\": 4, \"best_hold\": 3, \"max_distance\": 12, \"margin\": 3}\n",
+                "]\n"
+            )
+        );
+        assert!(to_json(&[RaceReport::new(&Race {
+            time: 7,
+            distance: 12
+        })])
+        .contains("\"first_win\": null"));
+        assert_eq!(to_json(&[]), "[]\n");
+    }
+}
@@ -0,0 +1,287 @@
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Race {
+    pub time: usize,
+    pub distance: usize,
+}
+
+/// How the columns of the sheet are read into races.
+#[derive(Debug, PartialEq)]
+pub enum ParseMode {
+    /// Every column is its own race.
+    Separate,
+    /// The spaces are bad kerning, so all columns form a single race.
+    Kerned,
+    /// Consecutive columns are joined into races of the given widths, e.g.
+    /// `[2, 1]` joins the first two columns and leaves the third alone.
+    Grouped(Vec<usize>),
+}
+
+/// Reads `separate`, `kerned` or a comma-separated list of group widths.
+impl std::str::FromStr for ParseMode {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "separate" => Ok(ParseMode::Separate),
+            "kerned" => Ok(ParseMode::Kerned),
+            _ => s
+                .split(',')
+                .map(|w| w.parse().map_err(|_| ParseError::BadMode(s.to_string())))
+                .collect::<Result<_, _>>()
+                .map(ParseMode::Grouped),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseError {
+    BadMode(String),
+    MissingRow(&'static str),
+    BadNumber(String),
+    CountMismatch { times: usize, distances: usize },
+    BadGrouping { columns: usize, grouped: usize },
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::BadMode(s) => write!(f, "unknown parse mode {:?}", s),
+            ParseError::MissingRow(name) => write!(f, "missing {} row", name),
+            ParseError::BadNumber(s) => write!(f, "invalid number {:?}", s),
+            ParseError::CountMismatch { times, distances } => {
+                write!(f, "found {} times but {} distances", times, distances)
+            }
+            ParseError::BadGrouping { columns, grouped } => write!(
+                f,
+                "grouping covers {} columns but the sheet has {}",
+                grouped, columns
+            ),
+        }
+    }
+}
+
+fn read_row<'a>(line: Option<&'a str>, name: &'static str) -> Result<Vec<&'a str>, ParseError> {
+    line.and_then(|s| s.strip_prefix(name))
+        .and_then(|s| s.strip_prefix(':'))
+        .map(|s| s.split_whitespace().collect())
+        .ok_or(ParseError::MissingRow(name))
+}
+
+/// Joins runs of columns into single numbers, `widths[i]` columns at a time.
+fn join_columns(columns: &[&str], widths: &[usize]) -> Result<Vec<usize>, ParseError> {
+    let mut numbers = vec![];
+    let mut rest = columns;
+
+    for width in widths {
+        let (group, tail) = rest.split_at((*width).min(rest.len()));
+        let digits = group.concat();
+        numbers.push(
+            digits
+                .parse::<usize>()
+                .map_err(|_| ParseError::BadNumber(digits))?,
+        );
+        rest = tail;
+    }
+
+    Ok(numbers)
+}
+
+pub fn parse_races(input: &str, mode: &ParseMode) -> Result<Vec<Race>, ParseError> {
+    let mut lines = input.split('\n');
+
+    let times = read_row(lines.next(), "Time")?;
+    let distances = read_row(lines.next(), "Distance")?;
+
+    if times.len() != distances.len() {
+        return Err(ParseError::CountMismatch {
+            times: times.len(),
+            distances: distances.len(),
+        });
+    }
+
+    let widths = match mode {
+        ParseMode::Separate => vec![1; times.len()],
+        ParseMode::Kerned => vec![times.len()],
+        ParseMode::Grouped(widths) => {
+            let grouped = widths.iter().sum();
+            if grouped != times.len() || widths.contains(&0) {
+                return Err(ParseError::BadGrouping {
+                    columns: times.len(),
+                    grouped,
+                });
+            }
+            widths.clone()
+        }
+    };
+
+    let times = join_columns(&times, &widths)?;
+    let distances = join_columns(&distances, &widths)?;
+
+    Ok(times
+        .into_iter()
+        .zip(distances)
+        .map(|(time, distance)| Race { time, distance })
+        .collect())
+}
+
+pub fn calculate_distance(hold_time: usize, total_time: usize) -> usize {
+    (total_time - hold_time) * hold_time
+}
+
+pub fn winning_moves(time: usize, winning_distance: usize) -> usize {
+    (1..time)
+        .map(|hold| calculate_distance(hold, time))
+        .filter(|distance| distance > &winning_distance)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_races() {
+        let input = include_str!("bin/test.txt");
+        assert_eq!(
+            parse_races(input, &ParseMode::Separate),
+            Ok(vec![
+                Race {
+                    time: 7,
+                    distance: 9
+                },
+                Race {
+                    time: 15,
+                    distance: 40
+                },
+                Race {
+                    time: 30,
+                    distance: 200
+                },
+            ])
+        );
+        assert_eq!(
+            parse_races("Time: 1 2 3\nDistance: 4 5 6", &ParseMode::Separate),
+            Ok(vec![
+                Race {
+                    time: 1,
+                    distance: 4
+                },
+                Race {
+                    time: 2,
+                    distance: 5
+                },
+                Race {
+                    time: 3,
+                    distance: 6
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_kerned() {
+        let input = include_str!("bin/test.txt");
+        assert_eq!(
+            parse_races(input, &ParseMode::Kerned),
+            Ok(vec![Race {
+                time: 71530,
+                distance: 940200
+            }])
+        );
+        assert_eq!(
+            parse_races("Time: 1 2 3\nDistance: 4 5", &ParseMode::Kerned),
+            Err(ParseError::CountMismatch {
+                times: 3,
+                distances: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_modes() {
+        let input = "Time: 1 2 3\nDistance: 4 5 6";
+
+        assert_eq!(
+            parse_races(input, &ParseMode::Kerned),
+            Ok(vec![Race {
+                time: 123,
+                distance: 456
+            }])
+        );
+        assert_eq!(
+            parse_races(input, &ParseMode::Grouped(vec![2, 1])),
+            Ok(vec![
+                Race {
+                    time: 12,
+                    distance: 45
+                },
+                Race {
+                    time: 3,
+                    distance: 6
+                },
+            ])
+        );
+        assert_eq!(
+            parse_races(input, &ParseMode::Grouped(vec![2, 2])),
+            Err(ParseError::BadGrouping {
+                columns: 3,
+                grouped: 4
+            })
+        );
+        assert_eq!(
+            parse_races(input, &ParseMode::Grouped(vec![0, 3])),
+            Err(ParseError::BadGrouping {
+                columns: 3,
+                grouped: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_mode() {
+        assert_eq!("separate".parse(), Ok(ParseMode::Separate));
+        assert_eq!("kerned".parse(), Ok(ParseMode::Kerned));
+        assert_eq!("2,1".parse(), Ok(ParseMode::Grouped(vec![2, 1])));
+        assert_eq!(
+            "2,x".parse::<ParseMode>(),
+            Err(ParseError::BadMode("2,x".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_races("Time: 1 2 3\nDistance: 4 5", &ParseMode::Separate),
+            Err(ParseError::CountMismatch {
+                times: 3,
+                distances: 2
+            })
+        );
+        assert_eq!(
+            parse_races("Time: 1 2 3", &ParseMode::Separate),
+            Err(ParseError::MissingRow("Distance"))
+        );
+        assert_eq!(
+            parse_races("Time: 1 x\nDistance: 4 5", &ParseMode::Separate),
+            Err(ParseError::BadNumber("x".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_calculate_distance() {
+        assert_eq!(calculate_distance(1, 7), 6);
+        assert_eq!(calculate_distance(2, 7), 10);
+        assert_eq!(calculate_distance(3, 7), 12);
+        assert_eq!(calculate_distance(4, 7), 12);
+        assert_eq!(calculate_distance(5, 7), 10);
+        assert_eq!(calculate_distance(6, 7), 6);
+    }
+
+    #[test]
+    fn test_winning_moves() {
+        assert_eq!(winning_moves(7, 9), 4);
+        assert_eq!(winning_moves(15, 40), 8);
+        assert_eq!(winning_moves(30, 200), 9);
+        assert_eq!(winning_moves(71530, 940200), 71503);
+    }
+}
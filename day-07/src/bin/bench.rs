@@ -0,0 +1,95 @@
+use std::time::Instant;
+
+use day_07::Ruleset;
+
+/// Times parsing and sorting a large batch of random hands. Takes the number
+/// of hands as an optional argument.
+fn main() {
+    let count = std::env::args()
+        .nth(1)
+        .map(|s| s.parse().unwrap())
+        .unwrap_or(1_000_000);
+
+    let lines = generate_hands(count, 0x2545f4914f6cdd1d);
+
+    let start = Instant::now();
+    let mut hands = Ruleset::camel().parse_hands(&lines.join("\n")).unwrap();
+    report("Parsed", count, start);
+
+    let start = Instant::now();
+    hands.sort_unstable_by_key(|hand| hand.key);
+    report("Sorted", count, start);
+
+    let winnings: usize = hands
+        .iter()
+        .enumerate()
+        .map(|(r, hand)| hand.bid.wrapping_mul(r + 1))
+        .fold(0, usize::wrapping_add);
+    println!("Winnings: {}", winnings);
+}
+
+fn report(action: &str, count: usize, start: Instant) {
+    let elapsed = start.elapsed();
+    println!(
+        "{} {} hands in {:.1} ms ({:.0} hands/s)",
+        action,
+        count,
+        elapsed.as_secs_f64() * 1000.0,
+        count as f64 / elapsed.as_secs_f64()
+    );
+}
+
+/// Random hand lines in the puzzle's format, from a xorshift generator so
+/// every run sorts the same hands.
+fn generate_hands(count: usize, seed: u64) -> Vec<String> {
+    let mut state = seed;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+
+    let alphabet = "23456789TJQKA".chars().collect::<Vec<_>>();
+    (0..count)
+        .map(|_| {
+            let cards = (0..5)
+                .map(|_| alphabet[next() as usize % alphabet.len()])
+                .collect::<String>();
+            format!("{} {}", cards, next() % 1000)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_hands() {
+        let ruleset = Ruleset::camel();
+        let lines = generate_hands(100, 1);
+
+        assert_eq!(lines, generate_hands(100, 1));
+        assert!(lines
+            .iter()
+            .all(|l| ruleset.parse_hand(l).unwrap().cards.len() == 5));
+    }
+
+    #[test]
+    fn test_key_sort_matches_cmp() {
+        let ruleset = Ruleset::camel();
+        let mut by_key = generate_hands(1000, 7)
+            .iter()
+            .map(|l| ruleset.parse_hand(l).unwrap())
+            .collect::<Vec<_>>();
+        by_key.sort_unstable_by_key(|hand| hand.key);
+
+        for pair in by_key.windows(2) {
+            let (a, b) = (&pair[0], &pair[1]);
+            assert!(
+                (ruleset.classify(&a.cards), &a.cards) <= (ruleset.classify(&b.cards), &b.cards)
+            );
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use std::{collections::BTreeMap, fs, time::Instant};
+
+use day_07::{classify, Evaluation, HandType, Ruleset, MAX_HAND_SIZE};
+
+/// Classifies every possible hand under a ruleset, e.g.
+/// `census [src/bin/rulesets/six.txt]`, and counts how often each type comes
+/// up with and without the ruleset's wildcards. Uses the jokers ruleset by
+/// default.
+fn main() {
+    let ruleset = match std::env::args().nth(1) {
+        Some(path) => fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|s| s.parse::<Ruleset>().map_err(|e| e.to_string()))
+            .unwrap_or_else(|e| {
+                eprintln!("Error: {}: {}", path, e);
+                std::process::exit(1);
+            }),
+        None => Ruleset::jokers(),
+    };
+
+    if let Evaluation::Poker { suits } = &ruleset.evaluation {
+        eprintln!(
+            "Error: the census covers camel rules, not poker with {} suits",
+            suits.len()
+        );
+        std::process::exit(1);
+    }
+
+    let start = Instant::now();
+    let counts = census(&ruleset);
+    let elapsed = start.elapsed();
+
+    print!("{}", to_table(&counts));
+    println!(
+        "Classified {} hands twice in {:.1} ms",
+        total(&counts, 0),
+        elapsed.as_secs_f64() * 1000.0
+    );
+}
+
+/// How many hands of each type there are, without and then with wildcards.
+type Census = BTreeMap<HandType, [usize; 2]>;
+
+/// Walks every hand in turn like an odometer, reusing one fixed array, so the
+/// only allocations are for types not seen before.
+fn census(ruleset: &Ruleset) -> Census {
+    let mut counts = Census::new();
+    let alphabet = ruleset.order.len() as u8;
+    let size = ruleset.hand_size;
+    let mut cards = [0u8; MAX_HAND_SIZE];
+
+    loop {
+        let hand = &cards[..size];
+        counts.entry(classify(hand, 0)).or_default()[0] += 1;
+        counts.entry(classify(hand, ruleset.wildcards)).or_default()[1] += 1;
+
+        let mut i = 0;
+        loop {
+            if i == size {
+                return counts;
+            }
+            cards[i] += 1;
+            if cards[i] < alphabet {
+                break;
+            }
+            cards[i] = 0;
+            i += 1;
+        }
+    }
+}
+
+fn total(counts: &Census, column: usize) -> usize {
+    counts.values().map(|c| c[column]).sum()
+}
+
+/// Lists the types from weakest to strongest, with each count as a share of
+/// all hands.
+fn to_table(counts: &Census) -> String {
+    let all = total(counts, 0) as f64;
+    let width = counts
+        .keys()
+        .map(|t| t.name().len())
+        .chain(["Total".len()])
+        .max()
+        .unwrap();
+
+    let mut out = format!(
+        "{:<width$}  {:>12}  {:>9}  {:>12}  {:>9}\n",
+        "Type",
+        "No jokers",
+        "%",
+        "Jokers",
+        "%",
+        width = width
+    );
+    for (hand_type, [plain, wild]) in counts.iter() {
+        out.push_str(&format!(
+            "{:<width$}  {:>12}  {:>8.4}%  {:>12}  {:>8.4}%\n",
+            hand_type.name(),
+            plain,
+            *plain as f64 / all * 100.0,
+            wild,
+            *wild as f64 / all * 100.0,
+            width = width
+        ));
+    }
+    out.push_str(&format!(
+        "{:<width$}  {:>12}  {:>9}  {:>12}\n",
+        "Total",
+        total(counts, 0),
+        "",
+        total(counts, 1),
+        width = width
+    ));
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hand_type(groups: &[u8]) -> HandType {
+        let mut padded = [0; MAX_HAND_SIZE];
+        padded[..groups.len()].copy_from_slice(groups);
+        HandType(padded)
+    }
+
+    fn count(counts: &Census, groups: &[u8]) -> [usize; 2] {
+        counts.get(&hand_type(groups)).copied().unwrap_or_default()
+    }
+
+    #[test]
+    fn test_census() {
+        let counts = census(&Ruleset::jokers());
+
+        assert_eq!(total(&counts, 0), 13usize.pow(5));
+        assert_eq!(total(&counts, 1), 13usize.pow(5));
+
+        // Ordered hands of five from thirteen ranks, worked out by hand
+        let expected = [
+            (vec![1, 1, 1, 1, 1], 13 * 12 * 11 * 10 * 9),
+            (vec![2, 1, 1, 1], 13 * 220 * 60),
+            (vec![2, 2, 1], 78 * 11 * 30),
+            (vec![3, 1, 1], 13 * 66 * 20),
+            (vec![3, 2], 13 * 12 * 10),
+            (vec![4, 1], 13 * 12 * 5),
+            (vec![5], 13),
+        ];
+        for (groups, plain) in expected.iter() {
+            assert_eq!(count(&counts, groups)[0], *plain);
+        }
+        assert_eq!(counts.len(), expected.len());
+
+        // Every hand with jokers and one other rank becomes five of a kind,
+        // and high cards can only be dealt without any
+        assert_eq!(count(&counts, &[5])[1], 12 * (2usize.pow(5) - 1) + 1);
+        assert_eq!(count(&counts, &[1, 1, 1, 1, 1])[1], 12 * 11 * 10 * 9 * 8);
+    }
+
+    #[test]
+    fn test_census_without_wildcards() {
+        let counts = census(&"alphabet = 234\nhand_size = 3".parse().unwrap());
+
+        assert_eq!(
+            counts.into_iter().collect::<Vec<_>>(),
+            vec![
+                (hand_type(&[1, 1, 1]), [6, 6]),
+                (hand_type(&[2, 1]), [18, 18]),
+                (hand_type(&[3]), [3, 3]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_to_table() {
+        let counts = census(
+            &"alphabet = 23\nwildcards = 2\nhand_size = 2"
+                .parse()
+                .unwrap(),
+        );
+
+        assert_eq!(
+            to_table(&counts),
+            concat!(
+                "Type          No jokers          %        Jokers          %\n",
+                "high card             2   50.0000%             0    0.0000%\n",
+                "one pair              2   50.0000%             4  100.0000%\n",
+                "Total                 4                        4\n",
+            )
+        );
+    }
+}
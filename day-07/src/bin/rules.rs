@@ -0,0 +1,269 @@
+use std::fs;
+
+use day_07::{Evaluation, Hand, Ruleset};
+
+/// Scores a set of hands under a ruleset read from a file, e.g.
+/// `rules src/bin/rulesets/jokers.txt [input]`. With `--explain` it lists
+/// every hand by rank along with how it got there.
+fn main() {
+    let explain = std::env::args().any(|arg| arg == "--explain");
+    let mut args = std::env::args().skip(1).filter(|arg| arg != "--explain");
+
+    let Some(path) = args.next() else {
+        eprintln!("Usage: rules <ruleset> [input]");
+        std::process::exit(1);
+    };
+    let ruleset = fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|s| s.parse::<Ruleset>().map_err(|e| e.to_string()))
+        .unwrap_or_else(|e| {
+            eprintln!("Error: {}: {}", path, e);
+            std::process::exit(1);
+        });
+
+    let input = match args.next() {
+        Some(path) => fs::read_to_string(&path).unwrap_or_else(|e| {
+            eprintln!("Error: {}: {}", path, e);
+            std::process::exit(1);
+        }),
+        None => include_str!("input.txt").to_string(),
+    };
+
+    if explain {
+        match ruleset.parse_hands(&input) {
+            Ok(hands) => print!("{}", to_table(&ruleset, &explain_hands(&ruleset, hands))),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    match ruleset.winnings(&input) {
+        Ok(result) => println!("Result: {}", result),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// One line of the `--explain` listing.
+#[derive(Debug, PartialEq)]
+struct Explanation {
+    rank: usize,
+    cards: String,
+    hand_type: String,
+    bid: usize,
+    winnings: usize,
+    /// Where this hand beat the one ranked just below it, if they are of the
+    /// same type.
+    tie_break: Option<String>,
+    /// The cards the wildcards stood in for, if there were any.
+    substitution: Option<String>,
+}
+
+/// Ranks the hands and explains each place in the order.
+fn explain_hands(ruleset: &Ruleset, mut hands: Vec<Hand>) -> Vec<Explanation> {
+    hands.sort_by_key(|hand| hand.key);
+
+    hands
+        .iter()
+        .enumerate()
+        .map(|(i, hand)| {
+            let cards = ruleset.format_cards(&hand.cards, &hand.suits);
+
+            let tie_break = i
+                .checked_sub(1)
+                .map(|j| &hands[j])
+                .filter(|below| below.key >> 32 == hand.key >> 32)
+                .map(|below| {
+                    let below_cards = ruleset.format_cards(&below.cards, &below.suits);
+                    let difference = (below.key ^ hand.key) as u32;
+                    let position = difference.leading_zeros() as usize / 4 + 1;
+
+                    match (difference, &ruleset.evaluation) {
+                        (0, _) => format!("tied with {}", below_cards),
+                        (_, Evaluation::Camel) => {
+                            format!("card {} over {}", position, below_cards)
+                        }
+                        (_, Evaluation::Poker { .. }) => {
+                            format!("tie-break card {} over {}", position, below_cards)
+                        }
+                    }
+                });
+
+            let substitution = ruleset.substitute(&hand.cards).map(|substituted| {
+                format!("as {}", ruleset.format_cards(&substituted, &hand.suits))
+            });
+
+            Explanation {
+                rank: i + 1,
+                cards,
+                hand_type: ruleset.type_name(hand),
+                bid: hand.bid,
+                winnings: hand.bid * (i + 1),
+                tie_break,
+                substitution,
+            }
+        })
+        .collect()
+}
+
+/// Lays the explanations out in columns, numbers on the right and text on the
+/// left, with a jokers column only for rulesets that have wildcards.
+fn to_table(ruleset: &Ruleset, explanations: &[Explanation]) -> String {
+    let jokers = ruleset.wildcards != 0;
+
+    let mut rows = vec![[
+        "Rank",
+        "Hand",
+        "Type",
+        "Bid",
+        "Winnings",
+        "Tie-break",
+        "Jokers",
+    ]
+    .map(|s| s.to_string())];
+    for e in explanations {
+        rows.push([
+            e.rank.to_string(),
+            e.cards.clone(),
+            e.hand_type.clone(),
+            e.bid.to_string(),
+            e.winnings.to_string(),
+            e.tie_break.clone().unwrap_or("-".to_string()),
+            e.substitution.clone().unwrap_or("-".to_string()),
+        ]);
+    }
+
+    let columns = if jokers { 7 } else { 6 };
+    let widths = (0..columns)
+        .map(|c| rows.iter().map(|row| row[c].chars().count()).max().unwrap())
+        .collect::<Vec<_>>();
+
+    let mut out = String::new();
+    for row in rows.iter() {
+        let cells = (0..columns)
+            .map(|c| match c {
+                0 | 3 | 4 => format!("{:>width$}", row[c], width = widths[c]),
+                _ => format!("{:<width$}", row[c], width = widths[c]),
+            })
+            .collect::<Vec<_>>();
+        out.push_str(cells.join("  ").trim_end());
+        out.push('\n');
+    }
+
+    let total: usize = explanations.iter().map(|e| e.winnings).sum();
+    out.push_str(&format!("Total winnings: {}\n", total));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poker() -> Ruleset {
+        include_str!("rulesets/poker.txt").parse().unwrap()
+    }
+
+    fn explain(ruleset: &Ruleset, input: &str) -> Vec<Explanation> {
+        explain_hands(ruleset, ruleset.parse_hands(input).unwrap())
+    }
+
+    #[test]
+    fn test_explain() {
+        let explanations = explain(&Ruleset::camel(), include_str!("test.txt"));
+
+        assert_eq!(
+            explanations
+                .iter()
+                .map(|e| (e.cards.as_str(), e.hand_type.as_str(), e.winnings))
+                .collect::<Vec<_>>(),
+            vec![
+                ("32T3K", "one pair", 765),
+                ("KTJJT", "two pair", 440),
+                ("KK677", "two pair", 84),
+                ("T55J5", "three of a kind", 2736),
+                ("QQQJA", "three of a kind", 2415),
+            ]
+        );
+        assert_eq!(
+            explanations
+                .iter()
+                .map(|e| e.tie_break.as_deref())
+                .collect::<Vec<_>>(),
+            vec![
+                None,
+                None,
+                Some("card 2 over KTJJT"),
+                None,
+                Some("card 1 over T55J5"),
+            ]
+        );
+        assert!(explanations.iter().all(|e| e.substitution.is_none()));
+
+        let tied = explain(&Ruleset::camel(), "KK677 1\nKK677 2");
+        assert_eq!(tied[1].tie_break.as_deref(), Some("tied with KK677"));
+    }
+
+    #[test]
+    fn test_explain_jokers() {
+        let explanations = explain(&Ruleset::jokers(), include_str!("test.txt"));
+
+        assert_eq!(
+            explanations
+                .iter()
+                .map(|e| (e.cards.as_str(), e.substitution.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("32T3K", None),
+                ("KK677", None),
+                ("T55J5", Some("as T5555")),
+                ("QQQJA", Some("as QQQQA")),
+                ("KTJJT", Some("as KTTTT")),
+            ]
+        );
+        assert_eq!(
+            explanations[3].tie_break.as_deref(),
+            Some("card 1 over T55J5")
+        );
+        assert_eq!(
+            explanations[4].tie_break.as_deref(),
+            Some("card 1 over QQQJA")
+        );
+    }
+
+    #[test]
+    fn test_explain_poker() {
+        let explanations = explain(&poker(), "AhAd9c5s2h 1\nAsAc9d4h3h 2");
+
+        assert_eq!(explanations[0].cards, "AsAc9d4h3h");
+        assert_eq!(explanations[1].hand_type, "one pair");
+        assert_eq!(
+            explanations[1].tie_break.as_deref(),
+            Some("tie-break card 4 over AsAc9d4h3h")
+        );
+    }
+
+    #[test]
+    fn test_to_table() {
+        let ruleset = Ruleset::jokers();
+        let table = to_table(&ruleset, &explain(&ruleset, include_str!("test.txt")));
+
+        assert_eq!(
+            table,
+            concat!(
+                "Rank  Hand   Type            Bid  Winnings  Tie-break          Jokers\n",
+                "   1  32T3K  one pair        765       765  -                  -\n",
+                "   2  KK677  two pair         28        56  -                  -\n",
+                "   3  T55J5  four of a kind  684      2052  -                  as T5555\n",
+                "   4  QQQJA  four of a kind  483      1932  card 1 over T55J5  as QQQQA\n",
+                "   5  KTJJT  four of a kind  220      1100  card 1 over QQQJA  as KTTTT\n",
+                "Total winnings: 5905\n",
+            )
+        );
+        assert!(!to_table(&Ruleset::camel(), &[]).contains("Jokers"));
+    }
+}
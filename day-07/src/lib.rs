@@ -0,0 +1,1137 @@
+use std::str::FromStr;
+
+pub fn to_lines(input: &str) -> Vec<&str> {
+    input.split("\n").collect()
+}
+
+/// The most cards a hand can hold, so that both its groups and its cards fit
+/// in a `u64` key at four bits each.
+pub const MAX_HAND_SIZE: usize = 8;
+
+/// The most cards an alphabet can have, so that every rank fits in four bits.
+const MAX_ALPHABET: usize = 16;
+
+/// The only order poker accepts. Straights, and the ace playing low in the
+/// wheel, are worked out from the ranks in this order.
+const POKER_ORDER: &str = "23456789TJQKA";
+
+/// How a hand's type is worked out.
+#[derive(Debug, PartialEq)]
+pub enum Evaluation {
+    /// Camel Cards: only groups of matching cards count.
+    Camel,
+    /// Standard poker, where every card also has one of `suits` and straights
+    /// and flushes count.
+    Poker { suits: Vec<char> },
+}
+
+/// The cards in play and how they rank against each other.
+#[derive(Debug, PartialEq)]
+pub struct Ruleset {
+    pub evaluation: Evaluation,
+    /// The alphabet from weakest to strongest. A card's rank is its index.
+    pub order: Vec<char>,
+    /// The ranks of the wildcards, as a bitmask.
+    pub wildcards: u16,
+    pub hand_size: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RulesetError {
+    Syntax { line: usize },
+    UnknownKey { line: usize, key: String },
+    InvalidHandSize { line: usize, value: String },
+    MissingAlphabet,
+    DuplicateCard(char),
+    TooManyCards(usize),
+    OrderMismatch,
+    UnknownWildcard(char),
+    UnknownEvaluation { line: usize, value: String },
+    DuplicateSuit(char),
+    Poker(&'static str),
+}
+
+impl std::fmt::Display for RulesetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RulesetError::Syntax { line } => write!(f, "line {}: expected `key = value`", line),
+            RulesetError::UnknownKey { line, key } => {
+                write!(f, "line {}: unknown key {:?}", line, key)
+            }
+            RulesetError::InvalidHandSize { line, value } => write!(
+                f,
+                "line {}: hand size must be 1 to {}, not {:?}",
+                line, MAX_HAND_SIZE, value
+            ),
+            RulesetError::MissingAlphabet => write!(f, "no alphabet given"),
+            RulesetError::DuplicateCard(c) => write!(f, "{:?} appears twice in the alphabet", c),
+            RulesetError::TooManyCards(n) => write!(
+                f,
+                "the alphabet has {} cards but at most {} are allowed",
+                n, MAX_ALPHABET
+            ),
+            RulesetError::OrderMismatch => {
+                write!(f, "the order must list every card of the alphabet once")
+            }
+            RulesetError::UnknownWildcard(c) => {
+                write!(f, "wildcard {:?} is not in the alphabet", c)
+            }
+            RulesetError::UnknownEvaluation { line, value } => write!(
+                f,
+                "line {}: evaluation must be camel or poker, not {:?}",
+                line, value
+            ),
+            RulesetError::DuplicateSuit(c) => write!(f, "suit {:?} appears twice", c),
+            RulesetError::Poker(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+/// Reads `key = value` lines, skipping blank lines and `#` comments. Only
+/// `alphabet` is required; `order` defaults to the alphabet, `wildcards` to
+/// none, `hand_size` to 5 and `evaluation` to camel. Poker rules need the
+/// standard card order and take their `suits` from the config, or `cdhs` if
+/// none are given.
+impl FromStr for Ruleset {
+    type Err = RulesetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut alphabet: Option<Vec<char>> = None;
+        let mut order: Option<Vec<char>> = None;
+        let mut wildcards = vec![];
+        let mut hand_size = 5;
+        let mut poker = false;
+        let mut suits: Option<Vec<char>> = None;
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .map(|(k, v)| (k.trim(), v.trim()))
+                .ok_or(RulesetError::Syntax { line: i + 1 })?;
+
+            match key {
+                "alphabet" => alphabet = Some(value.chars().collect()),
+                "order" => order = Some(value.chars().collect()),
+                "wildcards" => wildcards = value.chars().collect(),
+                "suits" => suits = Some(value.chars().collect()),
+                "evaluation" => match value {
+                    "camel" => poker = false,
+                    "poker" => poker = true,
+                    _ => {
+                        return Err(RulesetError::UnknownEvaluation {
+                            line: i + 1,
+                            value: value.to_string(),
+                        })
+                    }
+                },
+                "hand_size" => {
+                    hand_size = value
+                        .parse()
+                        .ok()
+                        .filter(|n| (1..=MAX_HAND_SIZE).contains(n))
+                        .ok_or(RulesetError::InvalidHandSize {
+                            line: i + 1,
+                            value: value.to_string(),
+                        })?
+                }
+                _ => {
+                    return Err(RulesetError::UnknownKey {
+                        line: i + 1,
+                        key: key.to_string(),
+                    })
+                }
+            }
+        }
+
+        let alphabet = alphabet
+            .filter(|a| !a.is_empty())
+            .ok_or(RulesetError::MissingAlphabet)?;
+        for (i, c) in alphabet.iter().enumerate() {
+            if alphabet[..i].contains(c) {
+                return Err(RulesetError::DuplicateCard(*c));
+            }
+        }
+        if alphabet.len() > MAX_ALPHABET {
+            return Err(RulesetError::TooManyCards(alphabet.len()));
+        }
+
+        let order = order.unwrap_or_else(|| alphabet.clone());
+        if order.len() != alphabet.len() || !alphabet.iter().all(|c| order.contains(c)) {
+            return Err(RulesetError::OrderMismatch);
+        }
+
+        let evaluation = match (poker, suits) {
+            (false, None) => Evaluation::Camel,
+            (false, Some(_)) => return Err(RulesetError::Poker("suits only apply to poker")),
+            (true, _) if hand_size != 5 => {
+                return Err(RulesetError::Poker("poker hands have five cards"))
+            }
+            (true, _) if !wildcards.is_empty() => {
+                return Err(RulesetError::Poker("poker has no wildcards"))
+            }
+            (true, suits) => {
+                let suits = suits.unwrap_or_else(|| "cdhs".chars().collect());
+                if suits.is_empty() {
+                    return Err(RulesetError::Poker("poker needs at least one suit"));
+                }
+                for (i, c) in suits.iter().enumerate() {
+                    if suits[..i].contains(c) {
+                        return Err(RulesetError::DuplicateSuit(*c));
+                    }
+                }
+                if !order.iter().copied().eq(POKER_ORDER.chars()) {
+                    return Err(RulesetError::Poker(
+                        "poker needs the standard 23456789TJQKA order",
+                    ));
+                }
+                Evaluation::Poker { suits }
+            }
+        };
+
+        let mut ruleset = Ruleset {
+            evaluation,
+            order,
+            wildcards: 0,
+            hand_size,
+        };
+        for c in wildcards {
+            let rank = ruleset
+                .rank(c)
+                .map_err(|_| RulesetError::UnknownWildcard(c))?;
+            ruleset.wildcards |= 1 << rank;
+        }
+
+        Ok(ruleset)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CardError(pub char);
+
+impl std::fmt::Display for CardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} is not a card", self.0)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum HandError {
+    InvalidCard { position: usize, error: CardError },
+    MissingSuit { position: usize },
+    DuplicateCard { position: usize },
+    WrongSize { expected: usize, found: usize },
+    MissingBid,
+    InvalidBid(String),
+}
+
+impl std::fmt::Display for HandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HandError::InvalidCard { position, error } => {
+                write!(f, "character {}: {}", position, error)
+            }
+            HandError::MissingSuit { position } => {
+                write!(f, "character {}: missing suit", position)
+            }
+            HandError::DuplicateCard { position } => {
+                write!(f, "character {}: card already dealt", position)
+            }
+            HandError::WrongSize { expected, found } => {
+                write!(f, "expected {} cards but found {}", expected, found)
+            }
+            HandError::MissingBid => write!(f, "missing bid"),
+            HandError::InvalidBid(bid) => write!(f, "invalid bid {:?}", bid),
+        }
+    }
+}
+
+/// A `HandError` along with the 1-based line it was found on.
+#[derive(Debug, PartialEq)]
+pub struct LineError {
+    pub line: usize,
+    pub error: HandError,
+}
+
+impl std::fmt::Display for LineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, {}", self.line, self.error)
+    }
+}
+
+/// A hand's type: the sizes of its groups of matching cards, largest first
+/// and padded with zeros. Comparing these in order puts five of a kind `[5]`
+/// over four of a kind `[4, 1]` over a full house `[3, 2]` and so on, which is
+/// the puzzle's order and carries over to any hand size.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct HandType(pub [u8; MAX_HAND_SIZE]);
+
+impl HandType {
+    pub fn name(&self) -> String {
+        const NUMBERS: [&str; 9] = [
+            "zero", "one", "two", "three", "four", "five", "six", "seven", "eight",
+        ];
+        let groups = self.0.iter().take_while(|g| **g > 1).collect::<Vec<_>>();
+
+        match groups[..] {
+            [] => "high card".to_string(),
+            [2] => "one pair".to_string(),
+            [2, 2] => "two pair".to_string(),
+            [3, 2] => "full house".to_string(),
+            [n] => format!("{} of a kind", NUMBERS[*n as usize]),
+            _ => {
+                let sizes = groups.iter().map(|g| g.to_string()).collect::<Vec<_>>();
+                format!("groups of {}", sizes.join("+"))
+            }
+        }
+    }
+}
+
+/// Groups the cards by rank without allocating. Wildcards, given as a
+/// bitmask of ranks, join the largest group, which always gives the best type.
+pub fn classify(cards: &[u8], wildcards: u16) -> HandType {
+    let mut counts = [0u8; MAX_ALPHABET];
+    let mut wild = 0;
+    for card in cards {
+        if wildcards & 1 << card != 0 {
+            wild += 1;
+        } else {
+            counts[*card as usize] += 1;
+        }
+    }
+
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    counts[0] += wild;
+
+    let mut groups = [0; MAX_HAND_SIZE];
+    groups.copy_from_slice(&counts[..MAX_HAND_SIZE]);
+    HandType(groups)
+}
+
+/// Standard poker hand categories, weakest first.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum PokerType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+impl PokerType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PokerType::HighCard => "high card",
+            PokerType::OnePair => "one pair",
+            PokerType::TwoPair => "two pair",
+            PokerType::ThreeOfAKind => "three of a kind",
+            PokerType::Straight => "straight",
+            PokerType::Flush => "flush",
+            PokerType::FullHouse => "full house",
+            PokerType::FourOfAKind => "four of a kind",
+            PokerType::StraightFlush => "straight flush",
+        }
+    }
+}
+
+/// A hand as card ranks, plus suits for poker. `key` packs its type above
+/// bit 32 and its tie-breaking ranks, four bits each, into the 32 bits below.
+/// A camel type is eight group sizes filling the top 32 bits; a poker
+/// category only takes bits 32 to 35.
+///
+/// Hands are ordered by strength alone, so comparing two hands is a single
+/// comparison of their keys. The bid plays no part in it, and neither do
+/// poker suits, so two hands of the same strength are equal whatever they
+/// bid.
+#[derive(Debug)]
+pub struct Hand {
+    pub bid: usize,
+    pub cards: Vec<u8>,
+    pub suits: Vec<u8>,
+    pub key: u64,
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Hand {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for Hand {}
+
+impl Ruleset {
+    /// The puzzle's rules, as in `rulesets/camel.txt`.
+    pub fn camel() -> Self {
+        include_str!("bin/rulesets/camel.txt").parse().unwrap()
+    }
+
+    /// The puzzle's second set of rules, with jacks as jokers, as in
+    /// `rulesets/jokers.txt`.
+    pub fn jokers() -> Self {
+        include_str!("bin/rulesets/jokers.txt").parse().unwrap()
+    }
+
+    pub fn rank(&self, card: char) -> Result<u8, CardError> {
+        self.order
+            .iter()
+            .position(|c| *c == card)
+            .map(|rank| rank as u8)
+            .ok_or(CardError(card))
+    }
+
+    /// The hand's type, with this ruleset's wildcards.
+    pub fn classify(&self, cards: &[u8]) -> HandType {
+        classify(cards, self.wildcards)
+    }
+
+    fn key(&self, cards: &[u8]) -> u64 {
+        let groups = self
+            .classify(cards)
+            .0
+            .iter()
+            .fold(0u64, |key, count| key << 4 | *count as u64);
+
+        cards
+            .iter()
+            .fold(groups, |key, card| key << 4 | *card as u64)
+            << (4 * (MAX_HAND_SIZE - cards.len()))
+    }
+
+    /// Works out a poker hand's category along with the ranks that break ties
+    /// within it: pairs and sets before their kickers, highest first. A
+    /// straight is ranked by its top card, with the ace playing low in the
+    /// wheel `A2345`.
+    fn classify_poker(&self, cards: &[u8], suits: &[u8]) -> (PokerType, [u8; 5]) {
+        let mut counts = [0u8; MAX_ALPHABET];
+        for card in cards {
+            counts[*card as usize] += 1;
+        }
+
+        let mut ranks = [0u8; 5];
+        ranks.copy_from_slice(cards);
+        ranks.sort_unstable_by(|a, b| counts[*b as usize].cmp(&counts[*a as usize]).then(b.cmp(a)));
+
+        let first = counts[ranks[0] as usize];
+        let second = ranks
+            .get(first as usize)
+            .map_or(0, |rank| counts[*rank as usize]);
+
+        let ace = self.order.len() as u8 - 1;
+        let straight = first == 1 && ranks[0] - ranks[4] == 4;
+        let wheel = first == 1 && ranks == [ace, 3, 2, 1, 0];
+        if wheel {
+            ranks.rotate_left(1);
+        }
+        let flush = suits.iter().all(|suit| *suit == suits[0]);
+
+        let poker_type = match (straight || wheel, flush, first, second) {
+            (true, true, _, _) => PokerType::StraightFlush,
+            (_, _, 4.., _) => PokerType::FourOfAKind,
+            (_, _, 3, 2) => PokerType::FullHouse,
+            (_, true, _, _) => PokerType::Flush,
+            (true, _, _, _) => PokerType::Straight,
+            (_, _, 3, _) => PokerType::ThreeOfAKind,
+            (_, _, 2, 2) => PokerType::TwoPair,
+            (_, _, 2, _) => PokerType::OnePair,
+            _ => PokerType::HighCard,
+        };
+
+        (poker_type, ranks)
+    }
+
+    /// Packs a poker hand so it sorts like `key`: the category in bits 32 to 35
+    /// and the five tie-breaking ranks below it, shifted up 12 bits to line up
+    /// with the cards of a camel key.
+    fn poker_key(&self, cards: &[u8], suits: &[u8]) -> u64 {
+        let (poker_type, ranks) = self.classify_poker(cards, suits);
+
+        ranks
+            .iter()
+            .fold(poker_type as u64, |key, rank| key << 4 | *rank as u64)
+            << (4 * (MAX_HAND_SIZE - ranks.len()))
+    }
+
+    fn suit(&self, suit: char) -> Option<u8> {
+        match &self.evaluation {
+            Evaluation::Camel => None,
+            Evaluation::Poker { suits } => suits.iter().position(|s| *s == suit).map(|s| s as u8),
+        }
+    }
+
+    /// Reads the card ranks, and for poker the suit after each of them.
+    fn parse_cards(&self, token: &str) -> Result<(Vec<u8>, Vec<u8>), HandError> {
+        let mut cards = vec![];
+        let mut suits = vec![];
+
+        let mut chars = token.chars().enumerate();
+        while let Some((i, c)) = chars.next() {
+            let rank = self.rank(c).map_err(|error| HandError::InvalidCard {
+                position: i + 1,
+                error,
+            })?;
+            cards.push(rank);
+
+            if self.evaluation != Evaluation::Camel {
+                let (i, c) = chars
+                    .next()
+                    .ok_or(HandError::MissingSuit { position: i + 2 })?;
+                let suit = self.suit(c).ok_or(HandError::InvalidCard {
+                    position: i + 1,
+                    error: CardError(c),
+                })?;
+
+                // `suits` doesn't have this card's suit yet, so the zip stops
+                // short of the card itself
+                let mut dealt = cards.iter().zip(suits.iter());
+                if dealt.any(|(r, s)| *r == rank && *s == suit) {
+                    return Err(HandError::DuplicateCard { position: i });
+                }
+                suits.push(suit);
+            }
+        }
+
+        Ok((cards, suits))
+    }
+
+    pub fn parse_hand(&self, s: &str) -> Result<Hand, HandError> {
+        let mut parts = s.split_whitespace();
+        let (cards, suits) = self.parse_cards(parts.next().unwrap_or_default())?;
+
+        if cards.len() != self.hand_size {
+            return Err(HandError::WrongSize {
+                expected: self.hand_size,
+                found: cards.len(),
+            });
+        }
+
+        let bid = parts.next().ok_or(HandError::MissingBid)?;
+        let bid = bid
+            .parse()
+            .map_err(|_| HandError::InvalidBid(bid.to_string()))?;
+
+        let key = match self.evaluation {
+            Evaluation::Camel => self.key(&cards),
+            Evaluation::Poker { .. } => self.poker_key(&cards, &suits),
+        };
+
+        Ok(Hand {
+            bid,
+            cards,
+            suits,
+            key,
+        })
+    }
+
+    /// Parses every non-blank line as a hand, stopping at the first bad one.
+    pub fn parse_hands(&self, input: &str) -> Result<Vec<Hand>, LineError> {
+        to_lines(input)
+            .iter()
+            .enumerate()
+            .filter(|(_, l)| !l.trim().is_empty())
+            .map(|(i, l)| {
+                self.parse_hand(l)
+                    .map_err(|error| LineError { line: i + 1, error })
+            })
+            .collect()
+    }
+
+    pub fn format_cards(&self, cards: &[u8], suits: &[u8]) -> String {
+        let mut out = String::new();
+
+        for (i, card) in cards.iter().enumerate() {
+            out.push(self.order[*card as usize]);
+            if let Evaluation::Poker { suits: names } = &self.evaluation {
+                out.push(names[suits[i] as usize]);
+            }
+        }
+
+        out
+    }
+
+    pub fn type_name(&self, hand: &Hand) -> String {
+        match self.evaluation {
+            Evaluation::Camel => self.classify(&hand.cards).name(),
+            Evaluation::Poker { .. } => self
+                .classify_poker(&hand.cards, &hand.suits)
+                .0
+                .name()
+                .to_string(),
+        }
+    }
+
+    /// The cards with every wildcard replaced by what it stood in for: a card
+    /// from the largest group, the strongest one if several are as large.
+    /// `None` if the hand has no wildcards.
+    pub fn substitute(&self, cards: &[u8]) -> Option<Vec<u8>> {
+        let is_wild = |card: &u8| self.wildcards & 1 << card != 0;
+        if !cards.iter().any(is_wild) {
+            return None;
+        }
+
+        let mut counts = [0u8; MAX_ALPHABET];
+        for card in cards.iter().filter(|c| !is_wild(c)) {
+            counts[*card as usize] += 1;
+        }
+
+        let target = (0..self.order.len() as u8)
+            .filter(|card| !is_wild(card))
+            .max_by_key(|card| (counts[*card as usize], *card))?;
+
+        Some(
+            cards
+                .iter()
+                .map(|card| if is_wild(card) { target } else { *card })
+                .collect(),
+        )
+    }
+
+    pub fn winnings(&self, input: &str) -> Result<usize, LineError> {
+        let mut hands = self.parse_hands(input)?;
+        hands.sort_unstable_by_key(|hand| hand.key);
+
+        Ok(hands
+            .iter()
+            .enumerate()
+            .map(|(r, hand)| hand.bid * (r + 1))
+            .sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranks(ruleset: &Ruleset, cards: &str) -> Vec<u8> {
+        cards.chars().map(|c| ruleset.rank(c).unwrap()).collect()
+    }
+
+    fn hand_type(groups: &[u8]) -> HandType {
+        let mut padded = [0; MAX_HAND_SIZE];
+        padded[..groups.len()].copy_from_slice(groups);
+        HandType(padded)
+    }
+
+    #[test]
+    fn test_to_lines() {
+        let input = "A\nB\nC";
+
+        assert_eq!(to_lines(input), ["A", "B", "C"]);
+    }
+
+    #[test]
+    fn test_parse_ruleset() {
+        let ruleset = Ruleset::camel();
+        assert_eq!(ruleset.order, "23456789TJQKA".chars().collect::<Vec<_>>());
+        assert_eq!(ruleset.wildcards, 0);
+        assert_eq!(ruleset.hand_size, 5);
+        assert_eq!(ruleset.rank('2'), Ok(0));
+        assert_eq!(ruleset.rank('A'), Ok(12));
+        assert_eq!(ruleset.rank('1'), Err(CardError('1')));
+
+        let ruleset = Ruleset::jokers();
+        assert_eq!(ruleset.rank('J'), Ok(0));
+        assert_eq!(ruleset.rank('T'), Ok(9));
+        assert_eq!(ruleset.wildcards, 1);
+
+        let ruleset: Ruleset = include_str!("bin/rulesets/six.txt").parse().unwrap();
+        assert_eq!(ruleset.hand_size, 6);
+    }
+
+    #[test]
+    fn test_ruleset_errors() {
+        let cases = [
+            ("alphabet 23", RulesetError::Syntax { line: 1 }),
+            (
+                "# comment\nalphabet = 23\nplayers = 4",
+                RulesetError::UnknownKey {
+                    line: 3,
+                    key: "players".to_string(),
+                },
+            ),
+            (
+                "alphabet = 23\nhand_size = 9",
+                RulesetError::InvalidHandSize {
+                    line: 2,
+                    value: "9".to_string(),
+                },
+            ),
+            ("hand_size = 5", RulesetError::MissingAlphabet),
+            ("alphabet = 232", RulesetError::DuplicateCard('2')),
+            (
+                "alphabet = 0123456789ABCDEFG",
+                RulesetError::TooManyCards(17),
+            ),
+            ("alphabet = 234\norder = 243A", RulesetError::OrderMismatch),
+            ("alphabet = 234\norder = 224", RulesetError::OrderMismatch),
+            (
+                "alphabet = 234\nwildcards = J",
+                RulesetError::UnknownWildcard('J'),
+            ),
+            (
+                "alphabet = 234\nevaluation = bridge",
+                RulesetError::UnknownEvaluation {
+                    line: 2,
+                    value: "bridge".to_string(),
+                },
+            ),
+            (
+                "alphabet = 234\nsuits = cd",
+                RulesetError::Poker("suits only apply to poker"),
+            ),
+            (
+                "alphabet = 234\nevaluation = poker\nhand_size = 6",
+                RulesetError::Poker("poker hands have five cards"),
+            ),
+            (
+                "alphabet = 234\nevaluation = poker\nwildcards = 2",
+                RulesetError::Poker("poker has no wildcards"),
+            ),
+            (
+                "alphabet = 234\nevaluation = poker\nsuits = cdc",
+                RulesetError::DuplicateSuit('c'),
+            ),
+            (
+                "alphabet = 234\nevaluation = poker",
+                RulesetError::Poker("poker needs the standard 23456789TJQKA order"),
+            ),
+            (
+                "alphabet = 23456789TJQKA\norder = 23456789TQJKA\nevaluation = poker",
+                RulesetError::Poker("poker needs the standard 23456789TJQKA order"),
+            ),
+        ];
+
+        for (config, error) in cases {
+            assert_eq!(config.parse::<Ruleset>(), Err(error));
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        let ruleset = Ruleset::camel();
+        let cases = [
+            ("23456", vec![1, 1, 1, 1, 1]),
+            ("A23A4", vec![2, 1, 1, 1]),
+            ("23432", vec![2, 2, 1]),
+            ("TTT98", vec![3, 1, 1]),
+            ("23332", vec![3, 2]),
+            ("AA8AA", vec![4, 1]),
+            ("AAAAA", vec![5]),
+        ];
+
+        for pair in cases.windows(2) {
+            assert!(hand_type(&pair[0].1) < hand_type(&pair[1].1));
+        }
+        for (cards, groups) in cases {
+            assert_eq!(
+                ruleset.classify(&ranks(&ruleset, cards)),
+                hand_type(&groups)
+            );
+        }
+    }
+
+    #[test]
+    fn test_classify_wildcards() {
+        let ruleset = Ruleset::jokers();
+        let cases = [
+            ("QJJQ2", vec![4, 1]),
+            ("T55J5", vec![4, 1]),
+            ("KTJJT", vec![4, 1]),
+            ("2345J", vec![2, 1, 1, 1]),
+            ("JJJJJ", vec![5]),
+            ("JJJJ2", vec![5]),
+            ("2233J", vec![3, 2]),
+        ];
+
+        for (cards, groups) in cases {
+            assert_eq!(
+                ruleset.classify(&ranks(&ruleset, cards)),
+                hand_type(&groups)
+            );
+        }
+    }
+
+    #[test]
+    fn test_key() {
+        let ruleset = Ruleset::camel();
+
+        // One pair, then the ranks of 3, 2, T, 3 and K
+        assert_eq!(
+            ruleset.key(&ranks(&ruleset, "32T3K")),
+            0x2111_0000_1081_B000
+        );
+        assert_eq!(
+            ruleset.key(&ranks(&ruleset, "AAAAA")),
+            0x5000_0000_CCCC_C000
+        );
+    }
+
+    #[test]
+    fn test_parse_hand() {
+        let ruleset = Ruleset::camel();
+
+        assert_eq!(ruleset.parse_hand("32T3K 765").map(|h| h.bid), Ok(765));
+        assert_eq!(
+            ruleset.parse_hand("44JJ9 449").map(|h| h.cards),
+            Ok(ranks(&ruleset, "44JJ9"))
+        );
+        assert_eq!(
+            ruleset.parse_hand("32X3K 765"),
+            Err(HandError::InvalidCard {
+                position: 3,
+                error: CardError('X')
+            })
+        );
+        assert_eq!(
+            ruleset.parse_hand("32T3 765"),
+            Err(HandError::WrongSize {
+                expected: 5,
+                found: 4
+            })
+        );
+        assert_eq!(ruleset.parse_hand("32T3K"), Err(HandError::MissingBid));
+        assert_eq!(
+            ruleset.parse_hand("32T3K 7x"),
+            Err(HandError::InvalidBid("7x".to_string()))
+        );
+        assert_eq!(
+            ruleset.parse_hands("32T3K 765\n32T3KK 765"),
+            Err(LineError {
+                line: 2,
+                error: HandError::WrongSize {
+                    expected: 5,
+                    found: 6
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_hands() {
+        let ruleset = Ruleset::camel();
+        let input = include_str!("bin/test.txt");
+        assert_eq!(ruleset.parse_hands(input).map(|hands| hands.len()), Ok(5));
+        assert_eq!(
+            ruleset
+                .parse_hands(&format!("{}\n", input))
+                .map(|h| h.len()),
+            Ok(5)
+        );
+
+        let error = ruleset
+            .parse_hands("32T3K 765\nT55J5 684\nKK6177 28")
+            .unwrap_err();
+        assert_eq!(
+            error,
+            LineError {
+                line: 3,
+                error: HandError::InvalidCard {
+                    position: 4,
+                    error: CardError('1')
+                }
+            }
+        );
+        assert_eq!(error.to_string(), "line 3, character 4: '1' is not a card");
+    }
+
+    fn hand(cards: &str) -> Hand {
+        Ruleset::camel().parse_hand(cards).unwrap()
+    }
+
+    #[test]
+    fn test_hand_ordering() {
+        let four_of_a_kind = hand("KKKK3 123");
+        let full_house = hand("22255 123");
+        assert!(four_of_a_kind > full_house);
+
+        let four_of_another_kind = hand("55554 123");
+        assert!(four_of_a_kind > four_of_another_kind);
+
+        let four_of_another_kind = hand("AKKKK 123");
+        assert!(four_of_a_kind < four_of_another_kind);
+    }
+
+    #[test]
+    fn test_bid_does_not_affect_order() {
+        let low_bid = hand("KK677 1");
+        let high_bid = hand("KK677 1000");
+
+        assert_eq!(low_bid.cmp(&high_bid), std::cmp::Ordering::Equal);
+        assert_eq!(low_bid, high_bid);
+
+        let mut hands = [hand("KK677 2"), hand("KTJJT 1")];
+        hands.sort();
+        assert_eq!(hands.iter().map(|h| h.bid).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    /// A small xorshift generator, so the property tests below can cover many
+    /// hands without pulling in a crate.
+    fn random_hands(count: usize, seed: u64) -> Vec<Hand> {
+        let mut state = seed;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        // A small alphabet makes pairs, ties and equal types common
+        let alphabet = ['2', '3', '9', 'T', 'J', 'A'];
+        (0..count)
+            .map(|_| {
+                let cards = (0..5)
+                    .map(|_| alphabet[next() as usize % alphabet.len()])
+                    .collect::<String>();
+                hand(&format!("{} {}", cards, next() % 1000))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cmp_agrees_with_partial_cmp() {
+        let hands = random_hands(200, 0x2545f4914f6cdd1d);
+
+        for a in hands.iter() {
+            for b in hands.iter() {
+                assert_eq!(a.partial_cmp(b), Some(a.cmp(b)));
+                assert_eq!(a.cmp(b), b.cmp(a).reverse());
+                assert_eq!(a == b, a.cmp(b) == std::cmp::Ordering::Equal);
+                assert_eq!(a == b, a.cards == b.cards);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cmp_is_transitive() {
+        let hands = random_hands(60, 0x9e3779b97f4a7c15);
+
+        for a in hands.iter() {
+            for b in hands.iter().filter(|b| a <= *b) {
+                for c in hands.iter().filter(|c| b <= *c) {
+                    assert!(a <= c);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sort_matches_strength() {
+        let ruleset = Ruleset::camel();
+        let mut hands = random_hands(500, 0xd1b54a32d192ed03);
+        hands.sort();
+
+        for pair in hands.windows(2) {
+            let (a, b) = (
+                ruleset.classify(&pair[0].cards),
+                ruleset.classify(&pair[1].cards),
+            );
+            assert!(a <= b);
+            if a == b {
+                assert!(pair[0].cards <= pair[1].cards);
+            }
+        }
+    }
+
+    #[test]
+    fn test_winnings() {
+        let input = include_str!("bin/test.txt");
+
+        assert_eq!(Ruleset::camel().winnings(input), Ok(6440));
+        assert_eq!(Ruleset::jokers().winnings(input), Ok(5905));
+        assert_eq!(
+            Ruleset::camel().winnings(include_str!("bin/input.txt")),
+            Ok(251287184)
+        );
+    }
+
+    #[test]
+    fn test_variants() {
+        let queens: Ruleset = include_str!("bin/rulesets/queens.txt").parse().unwrap();
+        let input = "KKKQ2 1\nQQQK2 2";
+        assert_eq!(Ruleset::camel().winnings(input), Ok(2 + 2));
+        assert_eq!(queens.winnings(input), Ok(1 + 4));
+
+        let six: Ruleset = include_str!("bin/rulesets/six.txt").parse().unwrap();
+        let input = "AAAKKK 1\nAAAAK2 2\n2345QK 3";
+        assert_eq!(six.winnings(input), Ok(2 + 6 + 3));
+        assert_eq!(
+            six.parse_hands("AAKKQ 1"),
+            Err(LineError {
+                line: 1,
+                error: HandError::WrongSize {
+                    expected: 6,
+                    found: 5
+                }
+            })
+        );
+    }
+
+    fn poker() -> Ruleset {
+        include_str!("bin/rulesets/poker.txt").parse().unwrap()
+    }
+
+    fn poker_key(cards: &str) -> u64 {
+        poker().parse_hand(&format!("{} 1", cards)).unwrap().key
+    }
+
+    fn poker_type(cards: &str) -> PokerType {
+        let ruleset = poker();
+        let token = cards
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+        let (cards, suits) = ruleset.parse_cards(&token).unwrap();
+        ruleset.classify_poker(&cards, &suits).0
+    }
+
+    #[test]
+    fn test_poker_types() {
+        let cases = [
+            ("2h 7d 9c Js Kh", PokerType::HighCard),
+            ("2h 2d 9c Js Kh", PokerType::OnePair),
+            ("2h 2d 9c 9s Kh", PokerType::TwoPair),
+            ("2h 2d 2c Js Kh", PokerType::ThreeOfAKind),
+            ("5h 6d 7c 8s 9h", PokerType::Straight),
+            ("Ah 2d 3c 4s 5h", PokerType::Straight),
+            ("Th Jd Qc Ks Ah", PokerType::Straight),
+            ("2h 7h 9h Jh Kh", PokerType::Flush),
+            ("2h 2d 2c Ks Kh", PokerType::FullHouse),
+            ("2h 2d 2c 2s Kh", PokerType::FourOfAKind),
+            ("9h Th Jh Qh Kh", PokerType::StraightFlush),
+            ("Ad 2d 3d 4d 5d", PokerType::StraightFlush),
+        ];
+
+        for (cards, expected) in cases {
+            assert_eq!(poker_type(cards), expected, "{}", cards);
+        }
+
+        // Straights don't wrap around the ace
+        assert_eq!(poker_type("Qh Kd Ac 2s 3h"), PokerType::HighCard);
+    }
+
+    #[test]
+    fn test_poker_tie_breaks() {
+        // Ordered from weakest to strongest
+        let hands = [
+            "2h3d4c5s7h",
+            "AhAd9c5s2h",
+            "AsAc9d5h3h",
+            "2h2d3c3sAh",
+            "9h9d5c5s2h",
+            "9s9c5d5h3h",
+            "Ah2d3c4s5h",
+            "2s3c4d5h6h",
+            "TsJcQdKhAh",
+            "3h7h9hJhKh",
+            "2h7h9hQhKh",
+            "3h3d3cKsKh",
+            "4h4d4c2s2h",
+            "Ad2d3d4d5d",
+            "TdJdQdKdAd",
+        ];
+
+        for pair in hands.windows(2) {
+            assert!(poker_key(pair[0]) < poker_key(pair[1]), "{:?}", pair);
+        }
+
+        // Suits never break ties
+        assert_eq!(poker_key("AhAd9c5s2h"), poker_key("AsAc9d5h2c"));
+    }
+
+    #[test]
+    fn test_poker_cards() {
+        let ruleset = poker();
+
+        assert_eq!(
+            ruleset.parse_hand("2h3d4c5s7 1"),
+            Err(HandError::MissingSuit { position: 10 })
+        );
+        assert_eq!(
+            ruleset.parse_hand("2h3x4c5s7h 1"),
+            Err(HandError::InvalidCard {
+                position: 4,
+                error: CardError('x')
+            })
+        );
+        assert_eq!(
+            ruleset.parse_hand("2h3d2h5s7h 1"),
+            Err(HandError::DuplicateCard { position: 5 })
+        );
+        assert_eq!(
+            ruleset.parse_hand("2h3d4c5s 1"),
+            Err(HandError::WrongSize {
+                expected: 5,
+                found: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_poker_winnings() {
+        assert_eq!(
+            poker().winnings(include_str!("bin/poker.txt")),
+            Ok(40 + 2 * 30 + 3 * 50 + 4 * 20 + 5 * 10)
+        );
+    }
+
+    #[test]
+    fn test_hand_type_names() {
+        let cases = [
+            (vec![1, 1, 1, 1, 1], "high card"),
+            (vec![2, 1, 1, 1], "one pair"),
+            (vec![2, 2, 1], "two pair"),
+            (vec![3, 1, 1], "three of a kind"),
+            (vec![3, 2], "full house"),
+            (vec![4, 1], "four of a kind"),
+            (vec![5], "five of a kind"),
+            (vec![6], "six of a kind"),
+            (vec![3, 3], "groups of 3+3"),
+            (vec![2, 2, 2], "groups of 2+2+2"),
+        ];
+
+        for (groups, name) in cases {
+            assert_eq!(hand_type(&groups).name(), name);
+        }
+    }
+
+    #[test]
+    fn test_substitute() {
+        let ruleset = Ruleset::jokers();
+        assert_eq!(ruleset.substitute(&ranks(&ruleset, "32T3K")), None);
+        assert_eq!(
+            ruleset.substitute(&ranks(&ruleset, "KTJJT")),
+            Some(ranks(&ruleset, "KTTTT"))
+        );
+
+        // The substitution always gives the type the wildcards were scored as
+        for hand in ["JJJJJ", "JJ2JJ", "2J3J4", "2233J", "J2345"] {
+            let cards = ranks(&ruleset, hand);
+            let substituted = ruleset.substitute(&cards).unwrap();
+
+            assert!(substituted.iter().all(|card| *card != 0));
+            assert_eq!(
+                Ruleset::camel().classify(&substituted),
+                ruleset.classify(&cards)
+            );
+        }
+    }
+}
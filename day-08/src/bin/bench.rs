@@ -0,0 +1,106 @@
+use std::{collections::HashMap, time::Instant};
+
+use day_08::{parse_input, Network};
+
+/// Times walking the network for a fixed number of steps, first through the
+/// interned `Network` and then through a map keyed by label as the puzzle
+/// solutions used to. Takes the number of steps as an optional argument.
+fn main() {
+    let steps = std::env::args()
+        .nth(1)
+        .map(|s| s.parse().unwrap())
+        .unwrap_or(100_000_000);
+    let input = include_str!("input.txt");
+
+    let (instructions, network) = parse_input(input).unwrap();
+    let start = Instant::now();
+    let end = walk(&network, &instructions, network.id("AAA").unwrap(), steps);
+    report("Interned", steps, start);
+
+    let (directions, nodes) = parse_labels(input);
+    let start = Instant::now();
+    let end_label = walk_labels(&nodes, &directions, "AAA", steps);
+    report("HashMap", steps, start);
+
+    assert_eq!(network.id(end_label), Some(end));
+    println!("Both walks ended at {}", end_label);
+}
+
+fn report(name: &str, steps: usize, start: Instant) {
+    let elapsed = start.elapsed();
+    println!(
+        "{:<8} {} steps in {:.1} ms ({:.0} steps/s)",
+        name,
+        steps,
+        elapsed.as_secs_f64() * 1000.0,
+        steps as f64 / elapsed.as_secs_f64()
+    );
+}
+
+/// Takes `steps` steps from `start`, ignoring where they lead.
+fn walk(network: &Network, instructions: &[u8], start: u32, steps: usize) -> u32 {
+    instructions
+        .iter()
+        .cycle()
+        .take(steps)
+        .fold(start, |node, direction| network.step(node, *direction))
+}
+
+/// The network as the puzzle solutions first read it, for comparison.
+fn parse_labels(input: &str) -> (Vec<char>, HashMap<&str, (&str, &str)>) {
+    let mut lines = input.lines();
+
+    let instructions = lines.next().unwrap().chars().collect::<Vec<_>>();
+    lines.next();
+
+    let parts = lines.map(|l| {
+        let mut parts = l.split(" = (");
+        let index = parts.next().unwrap();
+        let mut directions = parts.next().unwrap().split(", ");
+        (
+            index,
+            (
+                directions.next().unwrap(),
+                directions.next().unwrap().strip_suffix(")").unwrap(),
+            ),
+        )
+    });
+
+    (instructions, parts.collect())
+}
+
+fn walk_labels<'a>(
+    nodes: &HashMap<&'a str, (&'a str, &'a str)>,
+    instructions: &[char],
+    start: &'a str,
+    steps: usize,
+) -> &'a str {
+    instructions
+        .iter()
+        .cycle()
+        .take(steps)
+        .fold(start, |node, direction| match direction {
+            'R' => nodes.get(node).unwrap().1,
+            'L' => nodes.get(node).unwrap().0,
+            _ => unreachable!(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_walks_agree() {
+        for input in [include_str!("test1.txt"), include_str!("input.txt")] {
+            let (instructions, network) = parse_input(input).unwrap();
+            let (directions, nodes) = parse_labels(input);
+
+            for steps in [0, 1, 2, 6, 1000] {
+                let end = walk(&network, &instructions, network.id("AAA").unwrap(), steps);
+                let end_label = walk_labels(&nodes, &directions, "AAA", steps);
+                assert_eq!(network.id(end_label), Some(end));
+            }
+        }
+    }
+}
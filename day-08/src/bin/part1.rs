@@ -1,46 +1,35 @@
-use std::collections::HashMap;
+use day_08::{parse_input, NetworkError};
 
 fn main() {
     let input = include_str!("input.txt");
-    let result = part1(input);
 
-    println!("Result: {}", result);
-}
-
-fn parse_input(input: &str) -> (impl Iterator<Item = char> + '_, HashMap<&str, (&str, &str)>) {
-    let mut lines = input.lines();
-
-    let instructions = lines.next().unwrap().chars().into_iter().cycle();
-    lines.next();
-
-    let parts = lines.map(|l| {
-            let mut parts = l.split(" = (");
-            let index = parts.next().unwrap();
-            let mut directions = parts.next().unwrap().split(", ");
-            (index, (directions.next().unwrap(), directions.next().unwrap().strip_suffix(")").unwrap()))
-        });
-
-    let nodes: HashMap<&str, (&str, &str)> = parts.collect();
-
-    (instructions, nodes)
+    match part1(input) {
+        Ok(result) => println!("Result: {}", result),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
 }
 
-fn part1(input: &str) -> u32 {
-    let (mut instructions, nodes) = parse_input(input);
+fn part1(input: &str) -> Result<u32, NetworkError> {
+    let (instructions, network) = parse_input(input)?;
+    let mut instructions = instructions.iter().cycle();
 
-    let mut current = "AAA";
+    let node = |label: &str| {
+        network
+            .id(label)
+            .ok_or_else(|| NetworkError::MissingNode(label.to_string()))
+    };
+    let mut current = node("AAA")?;
+    let end = node("ZZZ")?;
     let mut step = 0;
-    while current != "ZZZ" {
+    while current != end {
         step += 1;
-        current = match instructions.next() {
-            Some('R') => nodes.get(current).unwrap().1,
-            Some('L') => nodes.get(current).unwrap().0,
-            _ => unreachable!(),
-        }
-
+        current = network.step(current, *instructions.next().unwrap());
     }
 
-    step
+    Ok(step)
 }
 
 #[cfg(test)]
@@ -50,9 +39,34 @@ mod tests {
     #[test]
     fn test_part1() {
         let input = include_str!("test1.txt");
-        assert_eq!(part1(input), 6);
+        assert_eq!(part1(input), Ok(6));
 
         let input = include_str!("test2.txt");
-        assert_eq!(part1(input), 2);
+        assert_eq!(part1(input), Ok(2));
+    }
+
+    #[test]
+    fn test_missing_node() {
+        let input = "L\n\nAAA = (AAA, AAA)";
+        assert_eq!(
+            part1(input),
+            Err(NetworkError::MissingNode("ZZZ".to_string()))
+        );
+
+        let input = "L\n\nZZZ = (ZZZ, ZZZ)";
+        assert_eq!(
+            part1(input),
+            Err(NetworkError::MissingNode("AAA".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_undefined_node() {
+        // The baseline would have walked into YYY and never left
+        let input = "L\n\nAAA = (YYY, ZZZ)\nZZZ = (ZZZ, ZZZ)";
+        assert_eq!(
+            part1(input),
+            Err(NetworkError::UndefinedNode("YYY".to_string()))
+        );
     }
 }
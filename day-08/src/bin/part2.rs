@@ -1,58 +1,44 @@
-use std::collections::HashMap;
+use day_08::{parse_input, NetworkError};
 
 fn main() {
     let input = include_str!("input.txt");
-    let result = part2(input);
 
-    println!("Result: {}", result);
+    match part2(input) {
+        Ok(result) => println!("Result: {}", result),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
 }
 
-fn parse_input(input: &str) -> (Vec<char>, HashMap<&str, (&str, &str)>) {
-    let mut lines = input.lines();
-
-    let instructions = lines.next().unwrap().chars().collect::<Vec<_>>();
-    lines.next();
-
-    let parts = lines.map(|l| {
-        let mut parts = l.split(" = (");
-        let index = parts.next().unwrap();
-        let mut directions = parts.next().unwrap().split(", ");
-        (
-            index,
-            (
-                directions.next().unwrap(),
-                directions.next().unwrap().strip_suffix(")").unwrap(),
-            ),
-        )
-    });
-
-    let nodes: HashMap<&str, (&str, &str)> = parts.collect();
+fn part2(input: &str) -> Result<usize, NetworkError> {
+    let (instructions, network) = parse_input(input)?;
 
-    (instructions, nodes)
-}
-
-fn part2(input: &str) -> usize {
-    let (instructions, nodes) = parse_input(input);
+    let mut is_end = vec![false; network.edges.len()];
+    for (label, id) in network.ids.iter() {
+        is_end[*id as usize] = label.ends_with('Z');
+    }
 
-    let current = nodes.keys().filter(|n| n.ends_with("A")).map(|s| *s);
+    let current = network
+        .ids
+        .iter()
+        .filter(|(label, _)| label.ends_with('A'))
+        .map(|(_, id)| *id);
 
     let steps = current.map(|s| {
         let mut instructions = instructions.iter().cycle();
         let mut c = s;
         let mut step = 0;
-        while !c.ends_with("Z") {
+        while !is_end[c as usize] {
             step += 1;
-            c = match instructions.next() {
-                Some('R') => nodes.get(c).unwrap().1,
-                Some('L') => nodes.get(c).unwrap().0,
-                _ => unreachable!(),
-            };
+            c = network.step(c, *instructions.next().unwrap());
         }
 
         step
     });
 
-    steps.fold(1, |acc, next| lcm(acc, next))
+    Ok(steps.fold(1, lcm))
 }
 
 fn lcm(a: usize, b: usize) -> usize {
@@ -75,6 +61,16 @@ mod tests {
     #[test]
     fn test_part2() {
         let input = include_str!("test3.txt");
-        assert_eq!(part2(input), 6);
+        assert_eq!(part2(input), Ok(6));
+    }
+
+    #[test]
+    fn test_undefined_node() {
+        // An undefined label ending in A must not become a start node
+        let input = "L\n\n11A = (11Z, 22A)\n11Z = (11Z, 11Z)";
+        assert_eq!(
+            part2(input),
+            Err(NetworkError::UndefinedNode("22A".to_string()))
+        );
     }
 }
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+/// The network with every node label interned as a dense id, so walking it is
+/// just indexing into `edges`.
+#[derive(Debug, PartialEq, Default)]
+pub struct Network<'a> {
+    pub ids: HashMap<&'a str, u32>,
+    /// The left and right neighbours of every node, by id.
+    pub edges: Vec<[u32; 2]>,
+    /// Whether every node, by id, has a line of its own yet.
+    defined: Vec<bool>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum NetworkError {
+    MissingInstructions,
+    InvalidInstruction {
+        position: usize,
+        found: char,
+    },
+    MalformedNode {
+        line: usize,
+    },
+    /// A node has more than one line of its own, so its edges are ambiguous.
+    DuplicateNode {
+        line: usize,
+        label: String,
+    },
+    /// A node is referenced from another node's line but has none of its own.
+    UndefinedNode(String),
+    /// A node the walk needs, such as `AAA`, is not in the network.
+    MissingNode(String),
+}
+
+impl std::fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NetworkError::MissingInstructions => write!(f, "no instructions given"),
+            NetworkError::InvalidInstruction { position, found } => write!(
+                f,
+                "instruction {}: expected L or R but found {:?}",
+                position, found
+            ),
+            NetworkError::MalformedNode { line } => {
+                write!(f, "line {}: expected `AAA = (BBB, CCC)`", line)
+            }
+            NetworkError::DuplicateNode { line, label } => {
+                write!(f, "line {}: node {} is already defined", line, label)
+            }
+            NetworkError::UndefinedNode(label) => {
+                write!(f, "node {} is referenced but never defined", label)
+            }
+            NetworkError::MissingNode(label) => write!(f, "there is no node {}", label),
+        }
+    }
+}
+
+impl<'a> Network<'a> {
+    /// The id for `label`, handing out the next one if it is new.
+    fn intern(&mut self, label: &'a str) -> u32 {
+        let next = self.ids.len() as u32;
+        let id = *self.ids.entry(label).or_insert(next);
+        if id == next {
+            self.edges.push([id, id]);
+            self.defined.push(false);
+        }
+        id
+    }
+
+    fn define(&mut self, label: &'a str, left: &'a str, right: &'a str) {
+        let id = self.intern(label);
+        self.edges[id as usize] = [self.intern(left), self.intern(right)];
+        self.defined[id as usize] = true;
+    }
+
+    /// Fails on the first node, by label, that was referenced but never
+    /// defined, since walking into it would go nowhere.
+    fn check_defined(&self) -> Result<(), NetworkError> {
+        match self
+            .ids
+            .iter()
+            .filter(|(_, id)| !self.defined[**id as usize])
+            .map(|(label, _)| *label)
+            .min()
+        {
+            Some(label) => Err(NetworkError::UndefinedNode(label.to_string())),
+            None => Ok(()),
+        }
+    }
+
+    pub fn id(&self, label: &str) -> Option<u32> {
+        self.ids.get(label).copied()
+    }
+
+    fn is_defined(&self, label: &str) -> bool {
+        self.id(label).is_some_and(|id| self.defined[id as usize])
+    }
+
+    pub fn step(&self, node: u32, direction: u8) -> u32 {
+        self.edges[node as usize][direction as usize]
+    }
+}
+
+/// Reads a node line of the form `AAA = (BBB, CCC)`. Labels are letters and
+/// digits only.
+fn parse_node(line: &str) -> Option<[&str; 3]> {
+    let (label, edges) = line.trim().split_once(" = (")?;
+    let (left, right) = edges.strip_suffix(')')?.split_once(", ")?;
+
+    let node = [label, left, right];
+    node.iter()
+        .all(|l| !l.is_empty() && l.chars().all(|c| c.is_ascii_alphanumeric()))
+        .then_some(node)
+}
+
+/// Reads the instructions as `0` for left and `1` for right, so they can index
+/// straight into a node's edges. Every non-blank line after them defines one
+/// node.
+pub fn parse_input(input: &str) -> Result<(Vec<u8>, Network<'_>), NetworkError> {
+    let mut lines = input.lines().enumerate();
+
+    let instructions = lines
+        .next()
+        .map(|(_, l)| l.trim())
+        .filter(|l| !l.is_empty())
+        .ok_or(NetworkError::MissingInstructions)?
+        .chars()
+        .enumerate()
+        .map(|(i, c)| match c {
+            'L' => Ok(0),
+            'R' => Ok(1),
+            _ => Err(NetworkError::InvalidInstruction {
+                position: i + 1,
+                found: c,
+            }),
+        })
+        .collect::<Result<_, _>>()?;
+
+    let mut network = Network::default();
+    for (i, l) in lines.filter(|(_, l)| !l.trim().is_empty()) {
+        let [label, left, right] =
+            parse_node(l).ok_or(NetworkError::MalformedNode { line: i + 1 })?;
+        if network.is_defined(label) {
+            return Err(NetworkError::DuplicateNode {
+                line: i + 1,
+                label: label.to_string(),
+            });
+        }
+
+        network.define(label, left, right);
+    }
+    network.check_defined()?;
+
+    Ok((instructions, network))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_input() {
+        let (instructions, network) = parse_input(include_str!("bin/test2.txt")).unwrap();
+
+        assert_eq!(instructions, vec![1, 0]);
+        assert_eq!(network.ids.len(), 7);
+        assert_eq!(network.edges.len(), 7);
+
+        let [aaa, bbb, ccc, zzz] = ["AAA", "BBB", "CCC", "ZZZ"].map(|l| network.id(l).unwrap());
+        assert_eq!(aaa, 0);
+        assert_eq!(network.edges[aaa as usize], [bbb, ccc]);
+        assert_eq!(network.step(aaa, 1), ccc);
+        assert_eq!(network.step(ccc, 0), zzz);
+        assert_eq!(network.edges[zzz as usize], [zzz, zzz]);
+        assert_eq!(network.id("XXX"), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let cases = [
+            ("", NetworkError::MissingInstructions),
+            ("\n\nAAA = (AAA, AAA)", NetworkError::MissingInstructions),
+            (
+                "LRX\n\nAAA = (AAA, AAA)",
+                NetworkError::InvalidInstruction {
+                    position: 3,
+                    found: 'X',
+                },
+            ),
+            (
+                "L\n\nAAA = (AAA, AAA)\nBBB = AAA, AAA",
+                NetworkError::MalformedNode { line: 4 },
+            ),
+            ("L\n\nAAA = (AAA)", NetworkError::MalformedNode { line: 3 }),
+            (
+                "L\n\nAAA = (AAA, AAA, AAA)",
+                NetworkError::MalformedNode { line: 3 },
+            ),
+            (
+                "L\n\nAAA = (AAA, )",
+                NetworkError::MalformedNode { line: 3 },
+            ),
+            (
+                "L\n\nAAA = (BBB, AAA)\nBBB = (AAA, AAA)\nAAA = (AAA, AAA)",
+                NetworkError::DuplicateNode {
+                    line: 5,
+                    label: "AAA".to_string(),
+                },
+            ),
+        ];
+
+        for (input, error) in cases {
+            assert_eq!(parse_input(input).err(), Some(error), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_blank_lines() {
+        let (instructions, network) =
+            parse_input("LR\n\nAAA = (AAA, BBB)\n\nBBB = (AAA, BBB)\n").unwrap();
+
+        assert_eq!(instructions, vec![0, 1]);
+        assert_eq!(network.ids.len(), 2);
+    }
+}